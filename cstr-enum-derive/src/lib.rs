@@ -1,19 +1,30 @@
-use quote::{quote, ToTokens};
-use proc_macro2::{Span};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+use syn::ext::IdentExt;
+use proc_macro2::{Span, TokenStream};
 use syn::parse::{Result, Error};
 use std::default::Default;
 use std::ffi::CStr;
 
 
-#[derive(Default)]
-struct VariantMeta {
-  pub name: Option<syn::LitByteStr>,
-}
+/// Shared parsing for the `#[cstr(...)]` attribute, which may appear on both the enum and its variants.
+trait CStrMeta: Default {
+  /// Parse a single item in the list of name-value pairs inside the #[cstr(...)]
+  fn parse_nv(&mut self, nv: syn::MetaNameValue) -> Result<()>;
+
+  /// Parse a single flag (an argument without a value) inside the #[cstr(...)]
+  fn parse_flag(&mut self, path: syn::Path) -> Result<()> {
+    Err(Error::new_spanned(path, "expected named argument (KEY = VALUE)"))
+  }
+
+  /// Parse a single nested list, such as `alias(...)`, inside the #[cstr(...)]
+  fn parse_list(&mut self, list: syn::MetaList) -> Result<()> {
+    Err(Error::new_spanned(list, "expected named argument (KEY = VALUE)"))
+  }
 
-impl VariantMeta {
-  /// Build the enum variant meta info from attributes
-  pub fn from_attrs(attrs: &[syn::Attribute]) -> Result<Self> {
-    let mut opts = VariantMeta::default();
+  /// Build the meta info from attributes
+  fn from_attrs(attrs: &[syn::Attribute]) -> Result<Self> {
+    let mut opts = Self::default();
 
     for attr in attrs {
       if attr.path.is_ident("cstr") {
@@ -23,13 +34,15 @@ impl VariantMeta {
     Ok(opts)
   }
 
-  /// Parse a single #[cstr(...)] item on a variant
-  pub fn parse_meta(&mut self, meta: syn::Meta) -> Result<()> {
+  /// Parse a single #[cstr(...)] item
+  fn parse_meta(&mut self, meta: syn::Meta) -> Result<()> {
     match meta {
       syn::Meta::List(nvs) => {
         for nv in nvs.nested {
           match nv {
             syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) => self.parse_nv(nv)?,
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) => self.parse_flag(path)?,
+            syn::NestedMeta::Meta(syn::Meta::List(list)) => self.parse_list(list)?,
             _ => return Err(Error::new_spanned(nv, "expected named argument (KEY = VALUE)"))
           }
         }
@@ -38,12 +51,326 @@ impl VariantMeta {
     }
     Ok(())
   }
+}
 
-  /// Parse a single item in the list of name-value pairs inside the #[cstr(...)]
+/// Check the field hasn't been set before by another attribute item
+fn check_not_set<T>(field: &Option<T>, tokens: impl ToTokens) -> Result<()> {
+  if field.is_some() {
+    Err(Error::new_spanned(tokens, "duplicate named argument"))
+  } else {
+    Ok(())
+  }
+}
+
+/// Convert a name to a nul-terminated byte string, checking that it has no nul bytes of its own
+fn name_to_lit(name: &[u8], span: Span) -> Result<syn::LitByteStr> {
+  let mut name = name.to_vec();
+  name.push(0);
+  if CStr::from_bytes_with_nul(&name).is_err() {
+    return Err(Error::new(span, "string cannot contain nul bytes"));
+  }
+  Ok(syn::LitByteStr::new(&name, span))
+}
+
+/// Parse a string literal giving a name, as a nul-terminated byte string.  A string literal may already end with the
+/// nul terminator, while a byte string literal is taken as is, for names which aren't UTF-8.
+fn parse_name_lit(lit: syn::Lit) -> Result<syn::LitByteStr> {
+  match lit {
+    syn::Lit::Str(s) => {
+      let value = s.value();
+      name_to_lit(value.strip_suffix('\0').unwrap_or(&value).as_bytes(), s.span())
+    },
+    syn::Lit::ByteStr(b) => name_to_lit(&b.value(), b.span()),
+    lit => Err(Error::new_spanned(lit, "expected string literal")),
+  }
+}
+
+/// Parse a string literal containing a path, such as `"path::to::item"`
+fn parse_lit_path(lit: &syn::Lit) -> Result<syn::Path> {
+  match lit {
+    syn::Lit::Str(s) => s.parse(),
+    lit => Err(Error::new_spanned(lit, "expected string literal")),
+  }
+}
+
+/// Parse a string literal containing a path to a trait method, such as `"path::Trait::method"`, into the path of the
+/// trait and the name of the method
+fn parse_trait_method(lit: &syn::Lit) -> Result<(syn::Path, syn::Ident)> {
+  let mut path = parse_lit_path(lit)?;
+  let method = match path.segments.pop() {
+    Some(method) if !path.segments.is_empty() && method.value().arguments.is_empty() => method.into_value().ident,
+    _ => return Err(Error::new_spanned(lit, "expected a path to a trait method, such as `\"path::Trait::method\"`")),
+  };
+  // Drop the trailing `::` left behind by the method
+  let last = path.segments.pop().unwrap().into_value();
+  path.segments.push(last);
+  Ok((path, method))
+}
+
+/// Case transformation applied to every name, see `#[cstr(normalize = "...")]`
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Normalize {
+  AsciiLowercase,
+  AsciiUppercase,
+}
+
+impl Normalize {
+  fn from_lit(s: &syn::LitStr) -> Result<Self> {
+    match s.value().as_str() {
+      "ascii_lowercase" => Ok(Normalize::AsciiLowercase),
+      "ascii_uppercase" => Ok(Normalize::AsciiUppercase),
+      _ => Err(Error::new_spanned(s, "expected one of \"ascii_lowercase\", \"ascii_uppercase\"")),
+    }
+  }
+
+  fn apply(self, bytes: &mut [u8]) {
+    match self {
+      Normalize::AsciiLowercase => bytes.make_ascii_lowercase(),
+      Normalize::AsciiUppercase => bytes.make_ascii_uppercase(),
+    }
+  }
+}
+
+/// Casing applied to the identifiers of variants without a name of their own, see `#[cstr(rename_all = "...")]`
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum RenameAll {
+  SnakeCase,
+  ScreamingSnakeCase,
+  KebabCase,
+  CamelCase,
+  Lowercase,
+}
+
+impl RenameAll {
+  fn from_lit(s: &syn::LitStr) -> Result<Self> {
+    match s.value().as_str() {
+      "snake_case" => Ok(RenameAll::SnakeCase),
+      "SCREAMING_SNAKE_CASE" => Ok(RenameAll::ScreamingSnakeCase),
+      "kebab-case" => Ok(RenameAll::KebabCase),
+      "camelCase" => Ok(RenameAll::CamelCase),
+      "lowercase" => Ok(RenameAll::Lowercase),
+      _ => Err(Error::new_spanned(
+        s,
+        "expected one of \"snake_case\", \"SCREAMING_SNAKE_CASE\", \"kebab-case\", \"camelCase\", \"lowercase\"",
+      )),
+    }
+  }
+
+  /// Rename an identifier, writing any of the `acronyms` in uppercase
+  fn apply(self, ident: &str, acronyms: &[String]) -> String {
+    let words: Vec<_> = split_words(ident, acronyms).into_iter().enumerate().map(|(i, (word, is_acronym))| match self {
+      _ if is_acronym => word.to_uppercase(),
+      RenameAll::ScreamingSnakeCase => word.to_uppercase(),
+      RenameAll::CamelCase if i > 0 => {
+        let mut chars = word.chars();
+        chars.next().map_or_else(String::new, |c| c.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect())
+      },
+      RenameAll::SnakeCase | RenameAll::KebabCase | RenameAll::CamelCase | RenameAll::Lowercase => word.to_lowercase(),
+    }).collect();
+    match self {
+      RenameAll::SnakeCase | RenameAll::ScreamingSnakeCase => words.join("_"),
+      RenameAll::KebabCase => words.join("-"),
+      RenameAll::CamelCase | RenameAll::Lowercase => words.concat(),
+    }
+  }
+}
+
+/// Split an identifier into words at underscores and changes of case, such as `HTTPServer_v2` into `HTTP`, `Server`
+/// and `v2`.  A word starting with one of the `acronyms`, ignoring ASCII case, ends after it if another word
+/// follows, so `HttpUrl` and `HTTPURL` both split into `HTTP` and `URL`.  Acronym words are marked `true`.
+fn split_words(ident: &str, acronyms: &[String]) -> Vec<(String, bool)> {
+  let mut words = Vec::new();
+  for part in ident.split('_') {
+    let chars: Vec<char> = part.chars().collect();
+    let word_starts_at = |k: usize| {
+      chars[k].is_uppercase() && (!chars[k - 1].is_uppercase() || chars.get(k + 1).is_some_and(|c| c.is_lowercase()))
+    };
+    let mut i = 0;
+    while i < chars.len() {
+      let acronym_end = acronyms.iter().filter_map(|a| {
+        let end = i + a.chars().count();
+        let matched = end <= chars.len() && chars[i..end].iter().zip(a.chars()).all(|(x, y)| x.eq_ignore_ascii_case(&y));
+        // The next word must start straight after the acronym
+        (matched && (end == chars.len() || chars[end].is_uppercase() || chars[end].is_numeric())).then_some(end)
+      }).max();
+      let end = acronym_end.unwrap_or_else(|| (i + 1..chars.len()).find(|&k| word_starts_at(k)).unwrap_or(chars.len()));
+      words.push((chars[i..end].iter().collect(), acronym_end.is_some()));
+      i = end;
+    }
+  }
+  words
+}
+
+/// Characters allowed in every name, see `#[cstr(validate_charset = "...")]`
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Charset {
+  Alnum,
+  AlnumUnderscore,
+  Printable,
+}
+
+impl Charset {
+  fn from_lit(s: &syn::LitStr) -> Result<Self> {
+    match s.value().as_str() {
+      "ascii_alnum" => Ok(Charset::Alnum),
+      "ascii_alnum_underscore" => Ok(Charset::AlnumUnderscore),
+      "ascii_printable" => Ok(Charset::Printable),
+      _ => Err(Error::new_spanned(s, "expected one of \"ascii_alnum\", \"ascii_alnum_underscore\", \"ascii_printable\"")),
+    }
+  }
+
+  fn allows(self, b: u8) -> bool {
+    match self {
+      Charset::Alnum => b.is_ascii_alphanumeric(),
+      Charset::AlnumUnderscore => b.is_ascii_alphanumeric() || b == b'_',
+      Charset::Printable => b.is_ascii_graphic() || b == b' ',
+    }
+  }
+}
+
+/// How `from_cstr` finds the name matching its input, see `#[cstr(lookup = "...")]`
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Lookup {
+  BinarySearch,
+}
+
+impl Lookup {
+  fn from_lit(s: &syn::LitStr) -> Result<Self> {
+    match s.value().as_str() {
+      "binary_search" => Ok(Lookup::BinarySearch),
+      _ => Err(Error::new_spanned(s, "expected \"binary_search\"")),
+    }
+  }
+}
+
+#[derive(Default)]
+struct ContainerMeta {
+  pub normalize: Option<Normalize>,
+  pub prefix_fallback: Option<syn::Path>,
+  pub unit_error: Option<syn::Path>,
+  pub emit_index: Option<syn::Path>,
+  pub emit_lengths: Option<syn::Path>,
+  pub outline: Option<syn::Path>,
+  pub trim: Option<syn::Path>,
+  pub no_string: Option<syn::Path>,
+  pub serde: Option<syn::Path>,
+  pub warn_style: Option<syn::Path>,
+  pub emit_c_header: Option<syn::Path>,
+  pub emit_metadata: Option<syn::Path>,
+  pub separator_insensitive: Option<syn::Path>,
+  pub krate: Option<syn::Path>,
+  pub total_bytes_max: Option<syn::LitInt>,
+  pub accept_ident: Option<syn::Path>,
+  pub case_insensitive: Option<syn::Path>,
+  pub emit_discriminant_conversions: Option<syn::Path>,
+  pub emit_c_module: Option<syn::Ident>,
+  pub deny_unparseable: Option<syn::Path>,
+  pub name_template: Option<syn::LitStr>,
+  pub prefix: Option<syn::LitStr>,
+  pub suffix: Option<syn::LitStr>,
+  pub lookup: Option<(Lookup, syn::LitStr)>,
+  pub validate_charset: Option<(Charset, syn::LitStr)>,
+  pub rename_with: Option<syn::Path>,
+  pub as_cstr_catchall: Option<syn::LitByteStr>,
+  pub rename_all: Option<(RenameAll, syn::LitStr)>,
+  pub error: Option<syn::Type>,
+  pub unknown_variant_error: Option<syn::Path>,
+  pub allow_duplicate_emit: Option<syn::Path>,
+  pub concat_names: Option<syn::Path>,
+  pub acronyms: Option<(syn::Path, Vec<String>)>,
+  /// Each `#[cstr(also_impl = "...")]`, split into the trait and the method
+  pub also_impl: Vec<(syn::Path, syn::Ident)>,
+}
+
+impl CStrMeta for ContainerMeta {
   fn parse_nv(&mut self, nv: syn::MetaNameValue) -> Result<()> {
     if let Some(ident) = nv.path.get_ident() {
-      if ident == "name" {
-        Self::check_not_set(&self.name, ident)?;
+      if ident == "normalize" {
+        check_not_set(&self.normalize, ident)?;
+        match nv.lit {
+          syn::Lit::Str(s) => {
+            self.normalize = Some(Normalize::from_lit(&s)?);
+            return Ok(());
+          }
+          lit => { return Err(Error::new_spanned(lit, "expected string literal")); }
+        }
+      } else if ident == "total_bytes_max" {
+        check_not_set(&self.total_bytes_max, ident)?;
+        match nv.lit {
+          syn::Lit::Int(i) => {
+            i.base10_parse::<usize>()?;
+            self.total_bytes_max = Some(i);
+            return Ok(());
+          }
+          lit => { return Err(Error::new_spanned(lit, "expected integer literal")); }
+        }
+      } else if ident == "emit_c_module" {
+        check_not_set(&self.emit_c_module, ident)?;
+        match nv.lit {
+          syn::Lit::Str(s) => {
+            self.emit_c_module = Some(s.parse()?);
+            return Ok(());
+          }
+          lit => { return Err(Error::new_spanned(lit, "expected string literal")); }
+        }
+      } else if ident == "name_template" {
+        check_not_set(&self.name_template, ident)?;
+        match nv.lit {
+          syn::Lit::Str(s) => {
+            let template = s.value();
+            if template.matches("{}").count() != 1 {
+              return Err(Error::new_spanned(s, "template must contain exactly one `{}`"));
+            }
+            if template.contains('\0') {
+              return Err(Error::new_spanned(s, "string cannot contain nul bytes"));
+            }
+            self.name_template = Some(s);
+            return Ok(());
+          }
+          lit => { return Err(Error::new_spanned(lit, "expected string literal")); }
+        }
+      } else if ident == "prefix" || ident == "suffix" {
+        let field = if ident == "prefix" { &mut self.prefix } else { &mut self.suffix };
+        check_not_set(field, ident)?;
+        match nv.lit {
+          syn::Lit::Str(s) => {
+            if s.value().contains('\0') {
+              return Err(Error::new_spanned(s, "string cannot contain nul bytes"));
+            }
+            *field = Some(s);
+            return Ok(());
+          }
+          lit => { return Err(Error::new_spanned(lit, "expected string literal")); }
+        }
+      } else if ident == "rename_all" {
+        check_not_set(&self.rename_all, ident)?;
+        match nv.lit {
+          syn::Lit::Str(s) => {
+            self.rename_all = Some((RenameAll::from_lit(&s)?, s));
+            return Ok(());
+          }
+          lit => { return Err(Error::new_spanned(lit, "expected string literal")); }
+        }
+      } else if ident == "validate_charset" {
+        check_not_set(&self.validate_charset, ident)?;
+        match nv.lit {
+          syn::Lit::Str(s) => {
+            self.validate_charset = Some((Charset::from_lit(&s)?, s));
+            return Ok(());
+          }
+          lit => { return Err(Error::new_spanned(lit, "expected string literal")); }
+        }
+      } else if ident == "lookup" {
+        check_not_set(&self.lookup, ident)?;
+        match nv.lit {
+          syn::Lit::Str(s) => {
+            self.lookup = Some((Lookup::from_lit(&s)?, s));
+            return Ok(());
+          }
+          lit => { return Err(Error::new_spanned(lit, "expected string literal")); }
+        }
+      } else if ident == "as_cstr_catchall" {
+        check_not_set(&self.as_cstr_catchall, ident)?;
         match nv.lit {
           syn::Lit::Str(s) => {
             let mut name = s.value();
@@ -51,11 +378,296 @@ impl VariantMeta {
             if CStr::from_bytes_with_nul(name.as_bytes()).is_err() {
               return Err(Error::new_spanned(s, "string cannot contain nul bytes"));
             }
-            self.name = Some(syn::LitByteStr::new(name.as_bytes(), s.span()));
+            self.as_cstr_catchall = Some(syn::LitByteStr::new(name.as_bytes(), s.span()));
             return Ok(());
           }
           lit => { return Err(Error::new_spanned(lit, "expected string literal")); }
         }
+      } else if ident == "error" {
+        check_not_set(&self.error, ident)?;
+        match nv.lit {
+          syn::Lit::Str(s) => {
+            self.error = Some(s.parse()?);
+            return Ok(());
+          }
+          lit => { return Err(Error::new_spanned(lit, "expected string literal")); }
+        }
+      } else if ident == "rename_with" {
+        check_not_set(&self.rename_with, ident)?;
+        self.rename_with = Some(parse_lit_path(&nv.lit)?);
+        return Ok(());
+      } else if ident == "also_impl" {
+        self.also_impl.push(parse_trait_method(&nv.lit)?);
+        return Ok(());
+      } else if ident == "crate" {
+        check_not_set(&self.krate, ident)?;
+        self.krate = Some(parse_lit_path(&nv.lit)?);
+        return Ok(());
+      } else if ident == "name" {
+        return Err(Error::new_spanned(nv, "attribute must be placed on variants"));
+      }
+    }
+    Err(Error::new_spanned(nv.path, "invalid named argument"))
+  }
+
+  fn parse_flag(&mut self, path: syn::Path) -> Result<()> {
+    if path.is_ident("prefix_fallback") {
+      check_not_set(&self.prefix_fallback, &path)?;
+      self.prefix_fallback = Some(path);
+      return Ok(());
+    } else if path.is_ident("unit_error") {
+      check_not_set(&self.unit_error, &path)?;
+      self.unit_error = Some(path);
+      return Ok(());
+    } else if path.is_ident("warn_style") {
+      check_not_set(&self.warn_style, &path)?;
+      self.warn_style = Some(path);
+      return Ok(());
+    } else if path.is_ident("trim") {
+      check_not_set(&self.trim, &path)?;
+      self.trim = Some(path);
+      return Ok(());
+    } else if path.is_ident("serde") {
+      check_not_set(&self.serde, &path)?;
+      self.serde = Some(path);
+      return Ok(());
+    } else if path.is_ident("no_string") {
+      check_not_set(&self.no_string, &path)?;
+      self.no_string = Some(path);
+      return Ok(());
+    } else if path.is_ident("outline") {
+      check_not_set(&self.outline, &path)?;
+      self.outline = Some(path);
+      return Ok(());
+    } else if path.is_ident("emit_lengths") {
+      check_not_set(&self.emit_lengths, &path)?;
+      self.emit_lengths = Some(path);
+      return Ok(());
+    } else if path.is_ident("emit_index") {
+      check_not_set(&self.emit_index, &path)?;
+      self.emit_index = Some(path);
+      return Ok(());
+    } else if path.is_ident("emit_discriminant_conversions") {
+      check_not_set(&self.emit_discriminant_conversions, &path)?;
+      self.emit_discriminant_conversions = Some(path);
+      return Ok(());
+    } else if path.is_ident("emit_metadata") {
+      check_not_set(&self.emit_metadata, &path)?;
+      self.emit_metadata = Some(path);
+      return Ok(());
+    } else if path.is_ident("emit_c_header") {
+      check_not_set(&self.emit_c_header, &path)?;
+      self.emit_c_header = Some(path);
+      return Ok(());
+    } else if path.is_ident("accept_ident") {
+      check_not_set(&self.accept_ident, &path)?;
+      self.accept_ident = Some(path);
+      return Ok(());
+    } else if path.is_ident("case_insensitive") {
+      check_not_set(&self.case_insensitive, &path)?;
+      self.case_insensitive = Some(path);
+      return Ok(());
+    } else if path.is_ident("separator_insensitive") {
+      check_not_set(&self.separator_insensitive, &path)?;
+      self.separator_insensitive = Some(path);
+      return Ok(());
+    } else if path.is_ident("concat_names") {
+      check_not_set(&self.concat_names, &path)?;
+      self.concat_names = Some(path);
+      return Ok(());
+    } else if path.is_ident("allow_duplicate_emit") {
+      check_not_set(&self.allow_duplicate_emit, &path)?;
+      self.allow_duplicate_emit = Some(path);
+      return Ok(());
+    } else if path.is_ident("unknown_variant_error") {
+      check_not_set(&self.unknown_variant_error, &path)?;
+      self.unknown_variant_error = Some(path);
+      return Ok(());
+    } else if path.is_ident("deny_unparseable") {
+      check_not_set(&self.deny_unparseable, &path)?;
+      self.deny_unparseable = Some(path);
+      return Ok(());
+    }
+    Err(Error::new_spanned(path, "invalid flag"))
+  }
+
+  fn parse_list(&mut self, list: syn::MetaList) -> Result<()> {
+    if list.path.is_ident("acronyms") {
+      check_not_set(&self.acronyms, &list.path)?;
+      let mut acronyms = Vec::with_capacity(list.nested.len());
+      for nested in &list.nested {
+        match nested {
+          syn::NestedMeta::Lit(syn::Lit::Str(s)) => {
+            let acronym = s.value();
+            if acronym.is_empty() || !acronym.bytes().all(|b| b.is_ascii_alphanumeric()) {
+              return Err(Error::new_spanned(s, "acronym must be ASCII letters and digits"));
+            }
+            acronyms.push(acronym);
+          }
+          _ => return Err(Error::new_spanned(nested, "expected string literal")),
+        }
+      }
+      self.acronyms = Some((list.path, acronyms));
+      return Ok(());
+    }
+    Err(Error::new_spanned(list.path, "invalid argument"))
+  }
+}
+
+impl ContainerMeta {
+  /// The path to the `cstr_enum` crate used by generated code, see `#[cstr(crate = "...")]`
+  fn krate(&self) -> syn::Path {
+    self.krate.clone().unwrap_or_else(|| syn::parse_quote! { ::cstr_enum })
+  }
+
+  /// The `Err` type of derived `FromCStr` and `FromCWStr` implementations
+  fn err_ty(&self, krate: &syn::Path) -> Result<TokenStream> {
+    if let Some(path) = &self.unknown_variant_error {
+      return match (&self.unit_error, &self.error) {
+        (Some(_), _) => Err(Error::new_spanned(path, "`unknown_variant_error` and `unit_error` cannot both be used")),
+        (None, Some(_)) => Err(Error::new_spanned(path, "`unknown_variant_error` and `error` cannot both be used")),
+        (None, None) => Ok(quote! { #krate::UnknownVariant }),
+      };
+    }
+    match (&self.unit_error, &self.error) {
+      (Some(_), Some(ty)) => Err(Error::new_spanned(ty, "`error` and `unit_error` cannot both be used")),
+      (Some(_), None) => Ok(quote! { () }),
+      (None, Some(ty)) => Ok(quote! { #ty }),
+      (None, None) => Ok(quote! { #krate::__private::Error }),
+    }
+  }
+
+  /// An expression for the error when parsing `ident` finds no match in the `input` bytes.  A type given by
+  /// `#[cstr(error = "...")]` is built from the message with `From<&'static str>`.
+  fn unknown_error(&self, krate: &syn::Path, ident: &syn::Ident, input: TokenStream) -> TokenStream {
+    let msg = syn::LitStr::new(&format!("unexpected string while parsing for {} variant", ident), Span::call_site());
+    let enum_name = syn::LitStr::new(&ident.to_string(), Span::call_site());
+    if self.unknown_variant_error.is_some() {
+      return quote! { #krate::__private::unknown_variant_input(#input, #enum_name) };
+    }
+    match (&self.unit_error, &self.error) {
+      (Some(_), _) => quote! { () },
+      (None, Some(ty)) => quote_spanned! { ty.span()=> <#ty as ::core::convert::From<&'static str>>::from(#msg) },
+      (None, None) => quote! { #krate::__private::unknown_variant(#msg, #enum_name, #input.is_empty()) },
+    }
+  }
+
+  /// An expression for the error when the input to parse is out of bounds, like `unknown_error`.  `buf` and `start`
+  /// are the arguments of `from_cstr_field`, and any part of the field which is in bounds is the input.
+  fn range_error(&self, krate: &syn::Path, ident: &syn::Ident) -> TokenStream {
+    let msg = syn::LitStr::new(&format!("field out of range while parsing for {} variant", ident), Span::call_site());
+    let enum_name = syn::LitStr::new(&ident.to_string(), Span::call_site());
+    if self.unknown_variant_error.is_some() {
+      return quote! { #krate::__private::unknown_variant_input(buf.get(start..).unwrap_or_default(), #enum_name) };
+    }
+    match (&self.unit_error, &self.error) {
+      (Some(_), _) => quote! { () },
+      (None, Some(ty)) => quote_spanned! { ty.span()=> <#ty as ::core::convert::From<&'static str>>::from(#msg) },
+      (None, None) => quote! { #krate::__private::out_of_range(#msg, #enum_name) },
+    }
+  }
+
+  fn matching(&self) -> Matching {
+    Matching {
+      ignore_case: self.normalize.is_some() || self.case_insensitive.is_some(),
+      any_separator: self.separator_insensitive.is_some(),
+    }
+  }
+}
+
+/// How the input of `from_cstr` is compared with the names
+#[derive(Clone, Copy)]
+struct Matching {
+  /// Ignore ASCII case, see `#[cstr(case_insensitive)]` and `#[cstr(normalize = "...")]`
+  ignore_case: bool,
+  /// Treat `-` and `_` as the same byte, see `#[cstr(separator_insensitive)]`
+  any_separator: bool,
+}
+
+impl Matching {
+  fn is_exact(self) -> bool {
+    !self.ignore_case && !self.any_separator
+  }
+
+  /// Compare two names at derive time, the same way the generated code does.
+  fn eq(self, a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(&x, &y)| self.byte_eq(x, y))
+  }
+
+  fn byte_eq(self, x: u8, y: u8) -> bool {
+    let sep = |b: u8| self.any_separator && (b == b'-' || b == b'_');
+    x == y || (self.ignore_case && x.eq_ignore_ascii_case(&y)) || (sep(x) && sep(y))
+  }
+}
+
+#[derive(Default)]
+struct VariantMeta {
+  pub name: Option<syn::LitByteStr>,
+  pub name_fn: Option<syn::Path>,
+  pub cstr: Option<syn::Path>,
+  pub default: Option<syn::Path>,
+  pub other: Option<syn::Path>,
+  pub accept_ident: Option<syn::Path>,
+  pub transparent: Option<syn::Path>,
+  pub skip: Option<syn::Path>,
+  pub canonical: Option<syn::Path>,
+  pub name_in: Option<syn::Path>,
+  pub verbatim: Option<syn::Path>,
+  pub aliases: Vec<AliasMeta>,
+}
+
+/// A single `#[cstr(alias = "...")]` or `#[cstr(alias(...))]` item
+struct AliasMeta {
+  value: syn::LitByteStr,
+  case_insensitive: Option<syn::Path>,
+}
+
+impl AliasMeta {
+  fn parse(list: syn::MetaList) -> Result<Self> {
+    let mut value = None;
+    let mut case_insensitive = None;
+    for nested in &list.nested {
+      match nested {
+        syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) if nv.path.is_ident("value") => {
+          check_not_set(&value, &nv.path)?;
+          value = Some(parse_name_lit(nv.lit.clone())?);
+        }
+        syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("case_insensitive") => {
+          check_not_set(&case_insensitive, path)?;
+          case_insensitive = Some(path.clone());
+        }
+        _ => return Err(Error::new_spanned(nested, "expected `value = \"...\"` or `case_insensitive`")),
+      }
+    }
+    match value {
+      Some(value) => Ok(AliasMeta { value, case_insensitive }),
+      None => Err(Error::new_spanned(list, "missing `value = \"...\"`")),
+    }
+  }
+}
+
+impl CStrMeta for VariantMeta {
+  fn parse_nv(&mut self, nv: syn::MetaNameValue) -> Result<()> {
+    if let Some(ident) = nv.path.get_ident() {
+      if ident == "name" {
+        check_not_set(&self.name, ident)?;
+        self.name = Some(parse_name_lit(nv.lit)?);
+        return Ok(());
+      } else if ident == "alias" {
+        self.aliases.push(AliasMeta { value: parse_name_lit(nv.lit)?, case_insensitive: None });
+        return Ok(());
+      } else if ident == "name_fn" {
+        check_not_set(&self.name_fn, ident)?;
+        self.name_fn = Some(parse_lit_path(&nv.lit)?);
+        return Ok(());
+      } else if ident == "cstr" {
+        check_not_set(&self.cstr, ident)?;
+        self.cstr = Some(parse_lit_path(&nv.lit)?);
+        return Ok(());
+      } else if ident == "name_in" {
+        check_not_set(&self.name_in, ident)?;
+        self.name_in = Some(parse_lit_path(&nv.lit)?);
+        return Ok(());
       }
       // future attributes can be added here.  Annoyingly, a match statement doesn't work
       // since `ident` is of a different type
@@ -64,13 +676,45 @@ impl VariantMeta {
     Err(Error::new_spanned(nv.path, "invalid named argument"))
   }
 
-  /// Check the field hasn't been set before by another attribute item
-  fn check_not_set<T>(field: &Option<T>, tokens: impl ToTokens) -> Result<()> {
-    if field.is_some() {
-      Err(Error::new_spanned(tokens, "duplicate named argument"))
-    } else {
-      Ok(())
+  fn parse_flag(&mut self, path: syn::Path) -> Result<()> {
+    if path.is_ident("default") {
+      check_not_set(&self.default, &path)?;
+      self.default = Some(path);
+      return Ok(());
+    } else if path.is_ident("other") {
+      check_not_set(&self.other, &path)?;
+      self.other = Some(path);
+      return Ok(());
+    } else if path.is_ident("accept_ident") {
+      check_not_set(&self.accept_ident, &path)?;
+      self.accept_ident = Some(path);
+      return Ok(());
+    } else if path.is_ident("transparent") {
+      check_not_set(&self.transparent, &path)?;
+      self.transparent = Some(path);
+      return Ok(());
+    } else if path.is_ident("skip") {
+      check_not_set(&self.skip, &path)?;
+      self.skip = Some(path);
+      return Ok(());
+    } else if path.is_ident("canonical") {
+      check_not_set(&self.canonical, &path)?;
+      self.canonical = Some(path);
+      return Ok(());
+    } else if path.is_ident("verbatim") {
+      check_not_set(&self.verbatim, &path)?;
+      self.verbatim = Some(path);
+      return Ok(());
     }
+    Err(Error::new_spanned(path, "invalid flag"))
+  }
+
+  fn parse_list(&mut self, list: syn::MetaList) -> Result<()> {
+    if list.path.is_ident("alias") {
+      self.aliases.push(AliasMeta::parse(list)?);
+      return Ok(());
+    }
+    Err(Error::new_spanned(list.path, "invalid argument"))
   }
 }
 
@@ -84,103 +728,1997 @@ fn ident_to_byte_str_lit(ident: &syn::Ident) -> syn::LitByteStr {
   syn::LitByteStr::new(cstring.as_bytes(), Span::call_site())
 }
 
-/// Check that #[cstr(...)] is not applied to the enum itself
-fn check_enum_attrs(input: &syn::DeriveInput) -> Result<()> {
-  for attr in &input.attrs {
-    if attr.path.is_ident("cstr") {
-      return Err(Error::new_spanned(attr, "attribute must be placed on variants"));
+/// Set the span of every token, so errors from code built from them point at `span`
+fn respan(tokens: impl ToTokens, span: Span) -> TokenStream {
+  tokens.into_token_stream().into_iter().map(|mut t| {
+    t.set_span(span);
+    t
+  }).collect()
+}
+
+/// The hidden constants holding each `name_fn` name.  The function is called and its result validated when the
+/// constant is evaluated, once per derive, so an invalid name is reported once rather than at every use.
+fn name_fn_consts(krate: &syn::Path, variants: &[Variant]) -> TokenStream {
+  let consts = variants.iter().filter_map(|v| match &v.name {
+    CName::Fn(path, name) => {
+      // Errors from evaluating the call should point at the path
+      let krate = respan(krate, path.span());
+      Some(quote_spanned! { path.span()=>
+          #[doc(hidden)]
+          #[allow(non_upper_case_globals)]
+          const #name: &::core::ffi::CStr = #krate::__private::cstr_from_name_fn(#path());
+      })
     }
+    _ => None,
+  });
+  quote! { #( #consts )* }
+}
+
+/// Emit a warning pointing at `span`.  There is no stable API for warnings from proc macros, so this
+/// uses a deprecated item instead.
+fn gen_warning(span: Span, msg: &str) -> TokenStream {
+  let note = syn::LitStr::new(msg, span);
+  quote_spanned! { span=>
+      const _: () = {
+          #[deprecated(note = #note)]
+          #[allow(non_camel_case_types)]
+          struct cstr_enum_warning;
+          let _ = cstr_enum_warning;
+      };
   }
-  Ok(())
 }
 
-/// Retrieve the name mapping between enum variants and their CStr representations
-fn get_name_mapping<'a>(input: &'a syn::DeriveInput, unit_variants_only: bool) -> Result<(Vec<&'a syn::Ident>, Vec<syn::LitByteStr>)> {
-  check_enum_attrs(input)?;
+/// The C string representation of a variant
+enum CName {
+  /// A literal, including the nul terminator
+  Lit(syn::LitByteStr),
+  /// A `const fn() -> &'static [u8]` returning nul-terminated bytes, evaluated once into the hidden constant
+  /// named by the ident (see `name_fn_consts`)
+  Fn(syn::Path, syn::Ident),
+  /// A `&'static CStr` constant
+  Const(syn::Path),
+  /// The result of calling a function with the ident of the variant, at runtime
+  Runtime(syn::Path, syn::LitStr),
+}
 
-  let variants = match &input.data {
-    syn::Data::Enum(enm) => &enm.variants,
-    _ => return Err(Error::new(Span::call_site(), "target must be an enum")),
-  };
+impl CName {
+  /// An expression for the name as a `&'static CStr`, usable in `const` contexts.
+  fn cstr(&self, krate: &syn::Path) -> TokenStream {
+    match self {
+      CName::Lit(v) => quote! { unsafe { ::core::ffi::CStr::from_bytes_with_nul_unchecked(#v) } },
+      CName::Fn(path, name) => quote_spanned! { path.span()=> #name },
+      // Naming the type makes a constant of any other type an error at the path
+      CName::Const(path) => quote_spanned! { path.span()=> {
+        const NAME: &::core::ffi::CStr = #path;
+        NAME
+      } },
+      // Called the first time the name is needed, then cached
+      CName::Runtime(path, ident) => {
+        let krate = respan(krate, path.span());
+        quote_spanned! { path.span()=> {
+          static NAME: #krate::__private::OnceLock<#krate::__private::CString> = #krate::__private::OnceLock::new();
+          NAME.get_or_init(|| #krate::__private::cstring_from_rename_with(#path(#ident))).as_c_str()
+        } }
+      },
+    }
+  }
 
-  let mut idents = Vec::with_capacity(variants.len());
-  let mut bytestrs = Vec::with_capacity(variants.len());
+  /// Whether the name is known at compile time, so it can be used in `const` contexts.
+  fn is_const(&self) -> bool {
+    !matches!(self, CName::Runtime(..))
+  }
 
-  #[allow(unused_variables)]
-  for variant in variants {
-    if unit_variants_only && variant.fields != syn::Fields::Unit {
-      return Err(Error::new_spanned(variant, "variant cannot have fields"));
+  /// An expression for the name as a `&'static str`, or an error if it is not valid UTF-8.
+  fn str(&self, krate: &syn::Path) -> Result<TokenStream> {
+    match self {
+      CName::Lit(v) => {
+        let bytes = Self::strip_nul(v).value();
+        match String::from_utf8(bytes) {
+          Ok(s) => Ok(quote! { #s }),
+          Err(_) => Err(Error::new_spanned(v, "name must be valid UTF-8 to convert into a `String`, or the enum must have `#[cstr(no_string)]`")),
+        }
+      }
+      // Checked when the constant is evaluated instead
+      CName::Fn(path, _) | CName::Const(path) => {
+        let cstr = self.cstr(krate);
+        let krate = respan(krate, path.span());
+        Ok(quote_spanned! { path.span()=> {
+            const NAME: &str = #krate::__private::cstr_to_str(#cstr);
+            NAME
+        } })
+      }
+      CName::Runtime(path, _) => {
+        let cstr = self.cstr(krate);
+        let krate = respan(krate, path.span());
+        Ok(quote_spanned! { path.span()=> #krate::__private::cstr_to_str(#cstr) })
+      }
     }
-    // parse name from attributes
-    let ident = &variant.ident;
-    let opts = VariantMeta::from_attrs(&variant.attrs)?;
+  }
 
-    // Default to the ident of the variant
-    bytestrs.push(opts.name.unwrap_or_else(|| ident_to_byte_str_lit(&ident)));
-    idents.push(ident);
+  /// The name encoded as UTF-16, without the nul terminator.  Only literal names can be encoded at compile time.
+  fn wide(&self) -> Result<Vec<u16>> {
+    match self {
+      CName::Lit(v) => match String::from_utf8(Self::strip_nul(v).value()) {
+        Ok(s) => Ok(s.encode_utf16().collect()),
+        Err(_) => Err(Error::new_spanned(v, "name must be valid UTF-8 to encode as UTF-16")),
+      },
+      CName::Fn(path, _) => Err(Error::new_spanned(path, "`name_fn` cannot be used with wide strings")),
+      CName::Const(path) => Err(Error::new_spanned(path, "`cstr` cannot be used with wide strings")),
+      CName::Runtime(path, _) => Err(Error::new_spanned(path, "`rename_with` cannot be used with wide strings")),
+    }
   }
-  Ok((idents, bytestrs))
-}
 
+  /// An expression for the bytes of the name, without the nul terminator, usable in `const` contexts
+  /// if `is_const`.
+  fn bytes(&self, krate: &syn::Path) -> TokenStream {
+    match self {
+      CName::Lit(v) => {
+        let v = Self::strip_nul(v);
+        quote! { #v }
+      },
+      CName::Fn(..) | CName::Const(_) | CName::Runtime(..) => {
+        let cstr = self.cstr(krate);
+        quote! { #cstr.to_bytes() }
+      }
+    }
+  }
 
-/// Derive macro for the [`AsCStr`] trait.  May only be applied to enums.
-#[proc_macro_derive(AsCStr, attributes(cstr))]
-pub fn derive_ascstr_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-  let input = syn::parse_macro_input!(input as syn::DeriveInput);
+  /// An expression for the length of the name in bytes, without the nul terminator.
+  fn len(&self, krate: &syn::Path) -> TokenStream {
+    match self {
+      CName::Lit(v) => {
+        let len = v.value().len() - 1;
+        quote! { #len }
+      },
+      CName::Fn(..) | CName::Const(_) | CName::Runtime(..) => {
+        let bytes = self.bytes(krate);
+        quote! { #bytes.len() }
+      }
+    }
+  }
 
+  fn strip_nul(v: &syn::LitByteStr) -> syn::LitByteStr {
+    let bytes = v.value();
+    syn::LitByteStr::new(&bytes[..bytes.len() - 1], v.span())
+  }
+}
 
-  let (var_idents, vals) = match get_name_mapping(&input, false) {
-    Ok(m) => m,
-    Err(e) => { return e.to_compile_error().into(); }
-  };
+/// Convert a variant ident to a snake-case field ident, eg `FooBar` to `foo_bar`.
+fn ident_to_snake_case(ident: &syn::Ident) -> syn::Ident {
+  let mut s = String::new();
+  for (i, c) in ident.to_string().chars().enumerate() {
+    if c.is_uppercase() {
+      if i > 0 && !s.ends_with('_') {
+        s.push('_');
+      }
+      s.extend(c.to_lowercase());
+    } else {
+      s.push(c);
+    }
+  }
+  // Variants such as `Type` would otherwise become keywords
+  if syn::parse_str::<syn::Ident>(&s).is_ok() {
+    syn::Ident::new(&s, ident.span())
+  } else {
+    syn::Ident::new_raw(&s, ident.span())
+  }
+}
 
-  let ident = &input.ident;
+/// An enum variant and its resolved options
+struct Variant<'a> {
+  ident: &'a syn::Ident,
+  fields: &'a syn::Fields,
+  name: CName,
+  /// `#[cstr(default)]`: parsed from unknown strings, as well as its own name
+  default: Option<syn::Path>,
+  /// `#[cstr(other)]`: parsed from (and capturing) any string not matching another variant
+  other: Option<syn::Path>,
+  /// Other names which are parsed as this variant, but never produced by `as_cstr`
+  aliases: Vec<Alias>,
+  /// `#[cstr(transparent)]`: the name is the single field, rather than `name`
+  transparent: Option<syn::Path>,
+  /// `#[cstr(skip)]`: never parsed, but still has a name for `as_cstr`
+  skip: bool,
+  /// `#[cstr(name_in = "...")]`: builds the name at runtime for `as_cstr_in`, instead of using `name`
+  name_in: Option<syn::Path>,
+  /// `#[cstr(name = "...")]`: the name was written out, rather than made from the ident
+  renamed: bool,
+}
 
-  let ts = quote! {
-       impl cstr_enum::AsCStr for #ident {
-            fn as_cstr(&self) -> &'static std::ffi::CStr {
-                match self {
-                    #( Self::#var_idents{..} => unsafe {std::ffi::CStr::from_bytes_with_nul_unchecked(#vals) }, )*
-                }
-            }
-       }
-    };
+/// A name which is parsed as a variant, but never produced by `as_cstr`
+struct Alias {
+  name: CName,
+  /// `#[cstr(alias(case_insensitive))]`: ignore ASCII case for this name, even if the enum doesn't
+  ignore_case: bool,
+  /// Written out with `alias`, rather than implied by `accept_ident`, so errors can point at it
+  written: bool,
+}
 
-  ts.into()
+impl Variant<'_> {
+  /// The name followed by any aliases, each with how it is compared to the input under the enum's `matching`
+  fn all_names(&self, matching: Matching) -> impl Iterator<Item = (&CName, Matching)> {
+    std::iter::once((&self.name, matching)).chain(self.aliases.iter().map(move |a| {
+      (&a.name, Matching { ignore_case: matching.ignore_case || a.ignore_case, ..matching })
+    }))
+  }
 }
 
+/// Retrieve the name mapping between enum variants and their CStr representations
+fn get_name_mapping<'a>(input: &'a syn::DeriveInput, container: &ContainerMeta, derive: &str, unit_variants_only: bool) -> Result<Vec<Variant<'a>>> {
+  let variants = match &input.data {
+    syn::Data::Enum(enm) => &enm.variants,
+    _ => return Err(Error::new(Span::call_site(), "target must be an enum")),
+  };
 
-/// Derive macro for the [`FromCStr`] trait.  May only be applied to enums whose variants have no fields.
-#[proc_macro_derive(FromCStr, attributes(cstr))]
-pub fn derive_fromcstr_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  if let Some((_, lit)) = &container.rename_all {
+    if container.name_template.is_some() {
+      return Err(Error::new_spanned(lit, "`rename_all` and `name_template` cannot both be used"));
+    }
+    if container.rename_with.is_some() {
+      return Err(Error::new_spanned(lit, "`rename_all` and `rename_with` cannot both be used"));
+    }
+  } else if let Some((path, _)) = &container.acronyms {
+    return Err(Error::new_spanned(path, "`acronyms` can only be used together with `rename_all`"));
+  } else if let Some(path) = &container.warn_style {
+    return Err(Error::new_spanned(path, "`warn_style` can only be used together with `rename_all`"));
+  }
+  let acronyms = container.acronyms.as_ref().map_or(&[][..], |(_, a)| a);
+
+  let mut mapping = Vec::with_capacity(variants.len());
+  let mut canonical = Vec::with_capacity(variants.len());
+
+  for (index, variant) in variants.iter().enumerate() {
+    // parse name from attributes
+    let ident = &variant.ident;
+    let opts = VariantMeta::from_attrs(&variant.attrs)?;
+    // a capturing `other` variant is checked separately
+    if unit_variants_only && opts.other.is_none() && variant.fields != syn::Fields::Unit {
+      return Err(Error::new_spanned(variant, "variant cannot have fields"));
+    }
+
+    if let Some(path) = &opts.transparent {
+      if !matches!(&variant.fields, syn::Fields::Unnamed(f) if f.unnamed.len() == 1) {
+        return Err(Error::new_spanned(path, "`transparent` variant must have a single unnamed field"));
+      }
+      if opts.name.is_some() || opts.name_fn.is_some() || opts.cstr.is_some() || !opts.aliases.is_empty() {
+        return Err(Error::new_spanned(path, "`transparent` variant cannot be renamed"));
+      }
+      if let Some(path) = &opts.name_in {
+        return Err(Error::new_spanned(path, "`transparent` and `name_in` cannot both be used"));
+      }
+    }
+
+    if opts.skip.is_some() {
+      if let Some(alias) = opts.aliases.first() {
+        return Err(Error::new_spanned(&alias.value, "`skip` and `alias` cannot both be used"));
+      }
+      let conflict = [("accept_ident", &opts.accept_ident), ("default", &opts.default), ("other", &opts.other)]
+        .iter().find_map(|(key, p)| p.as_ref().map(|p| (*key, p)));
+      if let Some((key, p)) = conflict {
+        return Err(Error::new_spanned(p, format!("`skip` and `{}` cannot both be used", key)));
+      }
+    }
+
+    let renamed = opts.name.is_some();
+    // The enum's `prefix` and `suffix` go around every literal name, before it is normalized
+    let verbatim = opts.verbatim.is_some();
+    let affixed = |lit: syn::LitByteStr| {
+      if verbatim || (container.prefix.is_none() && container.suffix.is_none()) {
+        return lit;
+      }
+      let value = lit.value();
+      let mut bytes = container.prefix.as_ref().map_or_else(Vec::new, |p| p.value().into_bytes());
+      bytes.extend_from_slice(&value[..value.len() - 1]);
+      if let Some(suffix) = &container.suffix {
+        bytes.extend_from_slice(suffix.value().as_bytes());
+      }
+      bytes.push(0);
+      syn::LitByteStr::new(&bytes, lit.span())
+    };
+    let name = match (opts.name, opts.name_fn, opts.cstr) {
+      (Some(_), Some(path), _) => return Err(Error::new_spanned(path, "`name` and `name_fn` cannot both be used")),
+      (Some(_), None, Some(path)) => return Err(Error::new_spanned(path, "`name` and `cstr` cannot both be used")),
+      (None, Some(_), Some(path)) => return Err(Error::new_spanned(path, "`name_fn` and `cstr` cannot both be used")),
+      (None, Some(path), None) => {
+        if container.normalize.is_some() {
+          return Err(Error::new_spanned(path, "`name_fn` cannot be used together with `normalize`"));
+        }
+        let name = format_ident!("__CSTR_ENUM_{}_{}_{}", derive, input.ident, index);
+        CName::Fn(path, name)
+      },
+      (None, None, Some(path)) => {
+        if container.normalize.is_some() {
+          return Err(Error::new_spanned(path, "`cstr` cannot be used together with `normalize`"));
+        }
+        CName::Const(path)
+      },
+      (None, None, None) if container.rename_with.is_some() => {
+        let path = container.rename_with.clone().unwrap();
+        if container.normalize.is_some() {
+          return Err(Error::new_spanned(path, "`rename_with` cannot be used together with `normalize`"));
+        }
+        if container.name_template.is_some() {
+          return Err(Error::new_spanned(path, "`rename_with` and `name_template` cannot both be used"));
+        }
+        CName::Runtime(path, syn::LitStr::new(&ident.to_string(), ident.span()))
+      },
+      (name, None, None) => {
+        // Default to the template filled in with the position of the variant, or else its (renamed) ident
+        let mut name = match (name, &container.name_template, &container.rename_all) {
+          (Some(name), _, _) => name,
+          (None, Some(template), _) => {
+            let name = format!("{}\0", template.value().replace("{}", &index.to_string()));
+            syn::LitByteStr::new(name.as_bytes(), template.span())
+          }
+          (None, None, Some((rename_all, _))) => name_to_lit(rename_all.apply(&ident.unraw().to_string(), acronyms).as_bytes(), ident.span())?,
+          (None, None, None) => ident_to_byte_str_lit(ident),
+        };
+        name = affixed(name);
+        if let Some(normalize) = container.normalize {
+          let mut bytes = name.value();
+          normalize.apply(&mut bytes);
+          name = syn::LitByteStr::new(&bytes, name.span());
+        }
+        CName::Lit(name)
+      }
+    };
+    let normalized = |lit: syn::LitByteStr| match container.normalize {
+      Some(normalize) => {
+        let mut bytes = lit.value();
+        normalize.apply(&mut bytes);
+        syn::LitByteStr::new(&bytes, lit.span())
+      }
+      None => lit,
+    };
+    let mut aliases = Vec::new();
+    if opts.accept_ident.is_some() || container.accept_ident.is_some() {
+      let ident_name = normalized(syn::LitByteStr::new(&ident_to_byte_str_lit(ident).value(), ident.span()));
+      // Nothing to do if the variant isn't renamed
+      if !matches!(&name, CName::Lit(n) if n.value() == ident_name.value()) {
+        aliases.push(Alias { name: CName::Lit(ident_name), ignore_case: false, written: false });
+      }
+    }
+    for alias in opts.aliases {
+      aliases.push(Alias { name: CName::Lit(normalized(affixed(alias.value))), ignore_case: alias.case_insensitive.is_some(), written: true });
+    }
+    mapping.push(Variant {
+      ident,
+      fields: &variant.fields,
+      name,
+      default: opts.default,
+      other: opts.other,
+      aliases,
+      transparent: opts.transparent,
+      skip: opts.skip.is_some(),
+      name_in: opts.name_in,
+      renamed,
+    });
+    canonical.push(opts.canonical);
+  }
+  check_duplicates(&mut mapping, &canonical, container)?;
+  if let Some((charset, lit)) = &container.validate_charset {
+    check_charset(&mapping, *charset, lit)?;
+  }
+  Ok(mapping)
+}
+
+/// With `#[cstr(warn_style)]`, warn about each `name` which `rename_all` would write differently, as it is probably
+/// out of style with the rest of the enum
+fn style_warnings(mapping: &[Variant], container: &ContainerMeta) -> TokenStream {
+  let (rename_all, lit) = match (&container.warn_style, &container.rename_all) {
+    (Some(_), Some((rename_all, lit))) => (rename_all, lit),
+    _ => return quote! {},
+  };
+  let acronyms = container.acronyms.as_ref().map_or(&[][..], |(_, a)| a);
+  mapping.iter().filter(|v| v.renamed).filter_map(|v| {
+    let name = match &v.name {
+      CName::Lit(name) => name,
+      _ => return None,
+    };
+    let bytes = name.value();
+    let written = std::str::from_utf8(&bytes[..bytes.len() - 1]).ok()?;
+    // The enum's `prefix` and `suffix` aren't part of the casing
+    let written = container.prefix.as_ref().and_then(|p| written.strip_prefix(&p.value())).unwrap_or(written);
+    let written = container.suffix.as_ref().and_then(|s| written.strip_suffix(&s.value())).unwrap_or(written);
+    // Identifiers can't have dashes, so they are only split at underscores
+    let styled = rename_all.apply(&written.replace('-', "_"), acronyms);
+    (styled != written).then(|| {
+      let msg = format!("name {:?} does not match `rename_all = {:?}`, which would write it as {:?}", written, lit.value(), styled);
+      gen_warning(name.span(), &msg)
+    })
+  }).collect()
+}
+
+/// Check that every literal name and alias only contains characters from `charset`.  Names which are only known
+/// later, such as those from `name_fn`, can't be checked.
+fn check_charset(mapping: &[Variant], charset: Charset, lit: &syn::LitStr) -> Result<()> {
+  let names = mapping.iter()
+    .filter(|v| v.transparent.is_none())
+    .flat_map(|v| std::iter::once(&v.name).chain(v.aliases.iter().map(|a| &a.name)));
+  for name in names {
+    if let CName::Lit(name) = name {
+      let bytes = name.value();
+      let bytes = &bytes[..bytes.len() - 1];
+      if let Some(&b) = bytes.iter().find(|&&b| !charset.allows(b)) {
+        let msg = format!(
+          "name {:?} contains '{}', which is not allowed by `validate_charset = {:?}`",
+          String::from_utf8_lossy(bytes),
+          std::ascii::escape_default(b),
+          lit.value(),
+        );
+        return Err(Error::new(name.span(), msg));
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Check that no two variants have the same literal name.  With `#[cstr(allow_duplicate_emit)]`, each group of
+/// variants sharing a name must instead have exactly one marked `#[cstr(canonical)]`, and the others are skipped
+/// when parsing.
+fn check_duplicates(mapping: &mut [Variant], canonical: &[Option<syn::Path>], container: &ContainerMeta) -> Result<()> {
+  let names: Vec<_> = mapping.iter().map(|v| match &v.name {
+    CName::Lit(name) if v.transparent.is_none() => Some(name.value()),
+    _ => None,
+  }).collect();
+  if container.allow_duplicate_emit.is_none() {
+    if let Some(path) = canonical.iter().flatten().next() {
+      return Err(Error::new_spanned(path, "`canonical` can only be used together with `allow_duplicate_emit` on the enum"));
+    }
+    for (i, name) in names.iter().enumerate() {
+      let prev = match name {
+        Some(name) => names[..i].iter().position(|n| n.as_ref() == Some(name)),
+        None => None,
+      };
+      if let (Some(prev), Some(name)) = (prev, name) {
+        let shown = String::from_utf8_lossy(&name[..name.len() - 1]);
+        let msg = format!("duplicate name {:?}, which is also the name of `{}`", shown, mapping[prev].ident);
+        return Err(Error::new_spanned(mapping[i].ident, msg));
+      }
+    }
+    return Ok(());
+  }
+  for (i, name) in names.iter().enumerate() {
+    let group: Vec<_> = (0..names.len()).filter(|&j| name.is_some() && names[j] == *name).collect();
+    if group.len() < 2 {
+      if let Some(path) = &canonical[i] {
+        return Err(Error::new_spanned(path, "`canonical` variant does not share its name with another variant"));
+      }
+      continue;
+    }
+    // Each group is only checked from its first variant
+    if group[0] != i {
+      continue;
+    }
+    let marked: Vec<_> = group.iter().copied().filter(|&j| canonical[j].is_some()).collect();
+    let keep = match marked[..] {
+      [keep] => keep,
+      [] => {
+        let msg = format!("`{}` has the same name as `{}`, so one of them must be marked `canonical`", mapping[group[1]].ident, mapping[i].ident);
+        return Err(Error::new_spanned(mapping[group[1]].ident, msg));
+      }
+      [_, second, ..] => {
+        return Err(Error::new_spanned(canonical[second].as_ref().unwrap(), "only one variant sharing a name may be marked `canonical`"));
+      }
+    };
+    for &j in group.iter().filter(|&&j| j != keep) {
+      if let Some(CName::Lit(alias)) = mapping[j].aliases.iter().find(|a| a.written).map(|a| &a.name) {
+        let msg = format!("`{}` is parsed as the canonical `{}`, so it cannot have an alias", mapping[j].ident, mapping[keep].ident);
+        return Err(Error::new_spanned(alias, msg));
+      }
+      mapping[j].skip = true;
+    }
+  }
+  Ok(())
+}
+
+/// Whether the enum has `#[repr(u8)]`, which may be one of several hints in the attribute
+fn has_repr_u8(attrs: &[syn::Attribute]) -> bool {
+  attrs.iter().filter(|a| a.path.is_ident("repr")).any(|a| match a.parse_meta() {
+    Ok(syn::Meta::List(list)) => list.nested.iter().any(|n| matches!(n, syn::NestedMeta::Meta(syn::Meta::Path(p)) if p.is_ident("u8"))),
+    _ => false,
+  })
+}
+
+/// The discriminant of each variant as a `u8`, if every explicit discriminant is an integer literal.  Otherwise they
+/// can only be computed by the compiler.
+fn literal_tags(input: &syn::DeriveInput) -> Option<Vec<u8>> {
+  let variants = match &input.data {
+    syn::Data::Enum(enm) => &enm.variants,
+    _ => return None,
+  };
+  let mut next = Some(0u8);
+  let mut tags = Vec::with_capacity(variants.len());
+  for v in variants {
+    let tag = match &v.discriminant {
+      Some((_, syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(i), .. }))) => i.base10_parse::<u8>().ok()?,
+      Some(_) => return None,
+      None => next?,
+    };
+    tags.push(tag);
+    next = tag.checked_add(1);
+  }
+  Some(tags)
+}
+
+/// The variant returned by `from_cstr` when the input matches no name
+enum Fallback<'a> {
+  /// A unit variant, from `#[cstr(default)]` or `#[cstr(other)]`
+  Unit(&'a syn::Ident),
+  /// A `#[cstr(other)]` variant holding the input as a `CString` or `Cow<CStr>`
+  Capture(&'a syn::Ident),
+}
+
+/// Find the `default` or `other` variant, if any
+fn get_fallback<'a>(variants: &[Variant<'a>]) -> Result<Option<Fallback<'a>>> {
+  let mut found: Option<(&syn::Path, Fallback)> = None;
+  for v in variants {
+    let path = match (&v.default, &v.other) {
+      (Some(_), Some(path)) => return Err(Error::new_spanned(path, "`default` and `other` cannot both be used")),
+      (Some(path), None) | (None, Some(path)) => path,
+      (None, None) => continue,
+    };
+    // The `other` variant only gets the input after every name has been tried, so it can't be given names
+    if let (Some(_), Some(alias)) = (&v.other, v.aliases.iter().find(|a| a.written)) {
+      if let CName::Lit(alias) = &alias.name {
+        return Err(Error::new_spanned(alias, "the `other` variant is parsed from every unknown string, so it cannot have an alias"));
+      }
+    }
+    if let Some((prev, _)) = &found {
+      let msg = if prev.is_ident("default") != path.is_ident("default") {
+        "`default` and `other` cannot both be used"
+      } else if path.is_ident("default") {
+        "only one variant may be marked `default`"
+      } else {
+        "only one variant may be marked `other`"
+      };
+      return Err(Error::new_spanned(path, msg));
+    }
+    let fallback = match v.fields {
+      syn::Fields::Unit => Fallback::Unit(v.ident),
+      syn::Fields::Unnamed(f) if v.other.is_some() && f.unnamed.len() == 1 && is_owned_cstr(&f.unnamed[0].ty) => Fallback::Capture(v.ident),
+      fields if v.other.is_some() => return Err(Error::new_spanned(fields, "`other` variant must be a unit variant or have a single `CString` or `Cow<CStr>` field")),
+      _ => return Err(Error::new_spanned(path, "`default` variant must be a unit variant")),
+    };
+    found = Some((path, fallback));
+  }
+  Ok(found.map(|(_, f)| f))
+}
+
+/// Whether `ty` names `CString` or `Cow<CStr>`, possibly qualified.  Both can be built from a `CString` with `From`.
+fn is_owned_cstr(ty: &syn::Type) -> bool {
+  let last = match ty {
+    syn::Type::Path(p) if p.qself.is_none() => p.path.segments.last(),
+    _ => None,
+  };
+  let last = match last {
+    Some(s) => s,
+    None => return false,
+  };
+  if last.ident == "CString" {
+    return last.arguments.is_empty();
+  }
+  match &last.arguments {
+    syn::PathArguments::AngleBracketed(args) if last.ident == "Cow" => args.args.iter().any(|a| matches!(
+      a,
+      syn::GenericArgument::Type(syn::Type::Path(p)) if p.path.segments.last().is_some_and(|s| s.ident == "CStr")
+    )),
+    _ => false,
+  }
+}
+
+/// Check that no input can match more than one of the `variants`, which is possible with aliases, or when
+/// matching is not exact.
+fn check_unambiguous(variants: &[&Variant], matching: Matching) -> Result<()> {
+  for (i, v) in variants.iter().enumerate() {
+    for (j, (name, name_matching)) in v.all_names(matching).enumerate() {
+      let name = match name {
+        CName::Lit(name) => name,
+        CName::Fn(..) | CName::Const(_) | CName::Runtime(..) => continue,
+      };
+      let is_alias = j > 0;
+      for prev in &variants[..i] {
+        for (k, (prev_name, prev_matching)) in prev.all_names(matching).enumerate() {
+          let prev_name = match prev_name {
+            CName::Lit(n) => n,
+            CName::Fn(..) | CName::Const(_) | CName::Runtime(..) => continue,
+          };
+          // Some input matches both names if they are equal when compared the looser way
+          let pair = Matching {
+            ignore_case: name_matching.ignore_case || prev_matching.ignore_case,
+            any_separator: matching.any_separator,
+          };
+          if !(is_alias || k > 0 || !pair.is_exact()) || !pair.eq(&name.value(), &prev_name.value()) {
+            continue;
+          }
+          return Err(if is_alias {
+            Error::new_spanned(name, format!("accepted name collides with a name of `{}`", prev.ident))
+          } else if k > 0 && prev.aliases[k - 1].written {
+            Error::new_spanned(prev_name, format!("accepted name collides with the name of `{}`", v.ident))
+          } else if k > 0 {
+            Error::new_spanned(v.ident, format!("name collides with an accepted name of `{}`", prev.ident))
+          } else {
+            let ignoring = match matching {
+              Matching { ignore_case: true, any_separator: true } => "case and separators",
+              Matching { ignore_case: true, any_separator: false } => "case",
+              _ => "separators",
+            };
+            Error::new_spanned(v.ident, format!("name is ambiguous with the name of `{}` when ignoring {}", prev.ident, ignoring))
+          });
+        }
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Like `gen_matcher`, but binary searching a table of the `names` sorted at compile time.  Only literal names
+/// can be sorted, and only exact matching is consistent with the order.
+fn gen_binary_search(krate: &syn::Path, bytes: &TokenStream, names: &[(&CName, Matching)], arms: &[TokenStream], lit: &syn::LitStr, fallback: &TokenStream) -> Result<TokenStream> {
+  if !names.iter().all(|(_, m)| m.is_exact()) {
+    return Err(Error::new_spanned(lit, "`binary_search` lookup requires exact matching"));
+  }
+  let mut sorted = Vec::with_capacity(names.len());
+  for ((name, _), arm) in names.iter().zip(arms) {
+    match name {
+      CName::Lit(v) => sorted.push((CName::strip_nul(v), arm)),
+      CName::Fn(path, _) | CName::Const(path) | CName::Runtime(path, _) => {
+        return Err(Error::new_spanned(path, "`binary_search` lookup requires literal names"));
+      }
+    }
+  }
+  sorted.sort_by_key(|(v, _)| v.value());
+  let (vals, arms): (Vec<_>, Vec<_>) = sorted.into_iter().unzip();
+  let indices = 0..vals.len();
+  Ok(quote! { {
+      const NAMES: &[&[u8]] = &[#( #vals ),*];
+      // Guards against the table above being emitted out of order
+      const _: () = assert!(#krate::__private::is_sorted(NAMES), "names must be sorted for `binary_search` lookup");
+      match NAMES.binary_search(&#bytes) {
+          #( Ok(#indices) => #arms, )*
+          _ => #fallback,
+      }
+  } })
+}
+
+/// The number of exactly matched literal names from which `gen_matcher` switches on the length of the input first.
+const LENGTH_DISPATCH_MIN_NAMES: usize = 16;
+
+/// Like the `match` generated by `gen_matcher` for exact literal names, but only comparing `bytes` with the names
+/// of the same length.  A long chain of slice comparisons is slow for enums with many variants.
+fn gen_length_dispatch(krate: &syn::Path, bytes: &TokenStream, names: &[(&CName, Matching)], arms: &[TokenStream], fallback: &TokenStream) -> TokenStream {
+  let mut by_len: Vec<(usize, Vec<(TokenStream, &TokenStream)>)> = Vec::new();
+  for ((name, _), arm) in names.iter().zip(arms) {
+    let len = match name {
+      CName::Lit(v) => v.value().len() - 1,
+      CName::Fn(..) | CName::Const(_) | CName::Runtime(..) => unreachable!("only literal names are dispatched by length"),
+    };
+    let val = name.bytes(krate);
+    match by_len.iter_mut().find(|(l, _)| *l == len) {
+      Some((_, group)) => group.push((val, arm)),
+      None => by_len.push((len, vec![(val, arm)])),
+    }
+  }
+  let groups = by_len.into_iter().map(|(len, group)| {
+    let (vals, arms): (Vec<_>, Vec<_>) = group.into_iter().unzip();
+    quote! {
+        #len => match #bytes {
+            #( #vals => break '__cstr_enum_found #arms, )*
+            _ => {}
+        },
+    }
+  });
+  // The fallback is only emitted once, rather than in every group
+  quote! {
+      '__cstr_enum_found: {
+          match #bytes.len() {
+              #( #groups )*
+              _ => {}
+          }
+          #fallback
+      }
+  }
+}
+
+/// Generate an expression comparing `bytes` (without the nul terminator) against each of the `names`, the way
+/// given alongside it, evaluating to the corresponding arm of the first match, or `fallback` if none match.
+fn gen_matcher(krate: &syn::Path, bytes: &TokenStream, names: &[(&CName, Matching)], arms: &[TokenStream], fallback: &TokenStream) -> TokenStream {
+  let vals = names.iter().map(|(n, _)| n.bytes(krate));
+  if names.iter().all(|(n, m)| m.is_exact() && matches!(n, CName::Lit(_))) {
+    if names.len() >= LENGTH_DISPATCH_MIN_NAMES {
+      return gen_length_dispatch(krate, bytes, names, arms, fallback);
+    }
+    quote! {
+        match #bytes {
+            #( #vals => #arms, )*
+            _ => #fallback
+        }
+    }
+  } else {
+    // Normalised names are stored in normal form, so comparing the input while ignoring case is the
+    // same as normalising it first, without needing a buffer.
+    let eqs = names.iter().map(|(_, m)| match *m {
+      Matching { ignore_case: false, any_separator: false } => quote! { <[u8]>::eq },
+      Matching { ignore_case: true, any_separator: false } => quote! { #krate::__private::eq_ignore_ascii_case },
+      Matching { ignore_case, any_separator: true } => quote! { #krate::__private::eq_any_separator::<#ignore_case> },
+    });
+    quote! {
+        #( if #eqs(#bytes, #vals) { #arms } else )* { #fallback }
+    }
+  }
+}
+
+
+/// Derive macro for the [`AsCStr`] trait.  May only be applied to enums.
+#[proc_macro_derive(AsCStr, attributes(cstr))]
+pub fn derive_ascstr_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
   let input = syn::parse_macro_input!(input as syn::DeriveInput);
 
-  let (var_idents, mut vals) = match get_name_mapping(&input, true) {
+  let container = match ContainerMeta::from_attrs(&input.attrs) {
+    Ok(c) => c,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  let krate = &container.krate();
+
+  let variants = match get_name_mapping(&input, &container, "AsCStr", false) {
     Ok(m) => m,
     Err(e) => { return e.to_compile_error().into(); }
   };
 
-  for v in vals.iter_mut() {
-    let bytes = v.value();
-    *v = syn::LitByteStr::new(&bytes[..bytes.len() - 1], v.span())
+  let ident = &input.ident;
+  let name_consts = name_fn_consts(krate, &variants);
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+  let enum_name = syn::LitStr::new(&ident.to_string(), Span::call_site());
+  // Opt-in, so that enums can still implement `Serialize` themselves when the feature is enabled
+  let serialize_impl = container.serde.as_ref().map(|_| quote! {
+      #krate::__private::if_serde! {
+          impl #impl_generics #krate::__private::serde::Serialize for #ident #ty_generics #where_clause {
+              fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+              where
+                  S: #krate::__private::serde::Serializer,
+              {
+                  #krate::__private::serialize_name(#krate::AsCStr::as_cstr(self), #enum_name, serializer)
+              }
+          }
+      }
+  });
+  let var_idents: Vec<_> = variants.iter().map(|v| v.ident).collect();
+  // Transparent variants have no fixed name
+  let named: Vec<_> = variants.iter().filter(|v| v.transparent.is_none()).collect();
+  // With `concat_names`, every name is a slice of one buffer, so the names are stored without any gaps
+  let concat = match &container.concat_names {
+    Some(path) => {
+      let lits = variants.iter().map(|v| match &v.name {
+        CName::Lit(name) if v.transparent.is_none() => Some(name.value()),
+        _ => None,
+      }).collect::<Option<Vec<_>>>();
+      let lits = match lits {
+        Some(lits) => lits,
+        None => return Error::new_spanned(path, "`concat_names` requires every variant to have a literal name").to_compile_error().into(),
+      };
+      let mut buf = Vec::new();
+      let mut ranges = Vec::with_capacity(lits.len());
+      for name in lits {
+        ranges.push((buf.len(), buf.len() + name.len()));
+        buf.extend(name);
+      }
+      Some((syn::LitByteStr::new(&buf, path.span()), ranges))
+    }
+    None => None,
+  };
+  let cstrs: Vec<_> = match &concat {
+    Some((_, ranges)) => ranges.iter().map(|(start, end)| quote! {
+        // Each range is one of the nul-terminated names
+        unsafe { ::core::ffi::CStr::from_bytes_with_nul_unchecked(#krate::__private::subslice(Self::__CSTR_ENUM_NAMES, #start, #end)) }
+    }).collect(),
+    None => named.iter().map(|v| v.name.cstr(krate)).collect(),
+  };
+  let as_cstr_arms = variants.iter().enumerate().map(|(k, v)| {
+    let var_ident = v.ident;
+    if concat.is_some() {
+      quote! { Self::#var_ident{..} => Self::CSTR_VARIANTS[#k] }
+    } else if v.transparent.is_some() {
+      quote! { Self::#var_ident(ref name) => ::core::convert::AsRef::<::core::ffi::CStr>::as_ref(name) }
+    } else {
+      let cstr = v.name.cstr(krate);
+      quote! { Self::#var_ident{..} => #cstr }
+    }
+  });
+  let as_cstr_lifetime = if named.len() == variants.len() { quote! { 'static } } else { quote! {} };
+  // Every variant has an arm, so the catch-all only makes sense if variants may be added later
+  let catchall_arm = match &container.as_cstr_catchall {
+    Some(name) if !input.attrs.iter().any(|a| a.path.is_ident("non_exhaustive")) => {
+      return Error::new_spanned(name, "`as_cstr_catchall` is unreachable unless the enum is `#[non_exhaustive]`").to_compile_error().into();
+    }
+    Some(name) => quote! {
+        #[allow(unreachable_patterns)]
+        _ => unsafe { ::core::ffi::CStr::from_bytes_with_nul_unchecked(#name) },
+    },
+    None => quote! {},
+  };
+  // Names from `rename_with` aren't available in `const` contexts
+  let runtime_names = named.iter().find_map(|v| match &v.name {
+    CName::Runtime(path, _) => Some(path),
+    _ => None,
+  });
+  let runtime_error = |option: &str| {
+    let msg = format!("`rename_with` names are computed at runtime, so they cannot be used with `{}`", option);
+    Error::new_spanned(runtime_names.unwrap(), msg).to_compile_error().into()
+  };
+  let names_list = if runtime_names.is_none() {
+    quote! { Self::CSTR_VARIANTS }
+  } else {
+    quote! { &[#( #cstrs ),*] }
+  };
+  let names_const = if runtime_names.is_none() {
+    // One constant per variant, named after the ident in SCREAMING_SNAKE_CASE
+    let mut consts: Vec<(String, &syn::Ident)> = Vec::with_capacity(named.len());
+    for v in &named {
+      let const_name = format!("CSTR_{}", RenameAll::ScreamingSnakeCase.apply(&v.ident.unraw().to_string(), &[]));
+      // These are already taken by other generated constants
+      if const_name == "CSTR_VARIANTS" || const_name == "CSTR_NAME_MAX_LEN"
+        || (const_name == "CSTR_VARIANT_LENS" && container.emit_lengths.is_some())
+        || ((const_name == "CSTR_MAPPING" || const_name == "CSTR_DISCRIMINANTS") && container.emit_metadata.is_some()) {
+        continue;
+      }
+      if let Some((_, prev)) = consts.iter().find(|(c, _)| *c == const_name) {
+        let msg = format!("`{}` and `{}` would both have the name constant `{}`", prev, v.ident, const_name);
+        return Error::new_spanned(v.ident, msg).to_compile_error().into();
+      }
+      consts.push((const_name, v.ident));
+    }
+    // Taken from `CSTR_VARIANTS`, so `name_fn` functions are only evaluated once
+    let variant_consts = named.iter().enumerate().filter_map(|(k, v)| {
+      let (const_name, _) = consts.iter().find(|(_, i)| *i == v.ident)?;
+      let const_ident = syn::Ident::new(const_name, v.ident.span());
+      let doc = format!("The name of [`{}::{}`](Self::{}).", ident, v.ident, v.ident);
+      Some(quote! {
+          #[doc = #doc]
+          pub const #const_ident: &'static ::core::ffi::CStr = Self::CSTR_VARIANTS[#k];
+      })
+    });
+    let lens_const = container.emit_lengths.as_ref().map(|_| {
+      let n = named.len();
+      let indices = 0..n;
+      quote! {
+          /// The length in bytes of each name in `CSTR_VARIANTS`, excluding the nul terminator.
+          pub const CSTR_VARIANT_LENS: [usize; #n] = [#( Self::CSTR_VARIANTS[#indices].to_bytes().len() ),*];
+      }
+    });
+    let concat_const = concat.as_ref().map(|(buf, _)| quote! {
+        #[doc(hidden)]
+        const __CSTR_ENUM_NAMES: &'static [u8] = #buf;
+    });
+    quote! {
+        /// The name of every variant, in declaration order, except for `transparent` variants.
+        pub const CSTR_VARIANTS: &'static [&'static ::core::ffi::CStr] = &[#( #cstrs ),*];
+
+        #concat_const
+
+        #lens_const
+
+        #( #variant_consts )*
+    }
+  } else {
+    quote! {}
+  };
+
+  let index_fns = if container.emit_index.is_some() {
+    if let Some(v) = variants.iter().find(|v| *v.fields != syn::Fields::Unit) {
+      return Error::new_spanned(v.fields, "variant cannot have fields when using `emit_index`").to_compile_error().into();
+    }
+    // Indices are positions in declaration order, regardless of any discriminants
+    let indices: Vec<_> = (0..variants.len()).collect();
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The position of this variant in declaration order, which is also its index in `CSTR_VARIANTS`.
+            ///
+            /// This is independent of any explicit discriminants.
+            pub const fn index(&self) -> usize {
+                match *self {
+                    #( Self::#var_idents => #indices, )*
+                }
+            }
+
+            /// The variant at position `index` in declaration order, the inverse of `index`.
+            pub const fn from_index(index: usize) -> Option<Self> {
+                match index {
+                    #( #indices => Some(Self::#var_idents), )*
+                    _ => None,
+                }
+            }
+        }
+    }
+  } else {
+    quote! {}
+  };
+
+  // Variants can only be constructed without any fields
+  let variants_fn = if variants.iter().all(|v| *v.fields == syn::Fields::Unit) {
+    let n_variants = variants.len();
+    // The slices have to live in constants, which can't hold names computed at runtime
+    let slices_fn = if runtime_names.is_none() {
+      quote! {
+          #[doc(hidden)]
+          const __CSTR_ENUM_ALL_VARIANTS: &'static [Self] = &[#( Self::#var_idents ),*];
+
+          /// Every variant and its name, as parallel slices in declaration order.
+          pub const fn all_variants_and_names() -> (&'static [Self], &'static [&'static ::core::ffi::CStr]) {
+              (Self::__CSTR_ENUM_ALL_VARIANTS, Self::CSTR_VARIANTS)
+          }
+      }
+    } else {
+      quote! {}
+    };
+    // With `binary_search` lookup the literal names can be sorted here, so only the matching range is scanned
+    let sorted = match &container.lookup {
+      Some((Lookup::BinarySearch, _)) => variants.iter().map(|v| match &v.name {
+        CName::Lit(name) => Some((CName::strip_nul(name), v.ident)),
+        CName::Fn(..) | CName::Const(_) | CName::Runtime(..) => None,
+      }).collect::<Option<Vec<_>>>(),
+      None => None,
+    };
+    let prefix_body = match sorted {
+      Some(mut sorted) => {
+        sorted.sort_by_key(|(name, _)| name.value());
+        let (vals, sorted_idents): (Vec<_>, Vec<_>) = sorted.into_iter().unzip();
+        quote! {
+            let prefix = prefix.to_bytes();
+            let names: [(Self, &'static [u8]); #n_variants] = [#( (Self::#sorted_idents, #vals) ),*];
+            let start = names.partition_point(|(_, name)| *name < prefix);
+            ::core::iter::IntoIterator::into_iter(names).skip(start)
+                .take_while(move |(_, name)| name.starts_with(prefix))
+                .map(|(v, _)| v)
+        }
+      }
+      None => {
+        let Matching { ignore_case, any_separator } = container.matching();
+        let names = if runtime_names.is_none() {
+          quote! { Self::CSTR_VARIANTS.iter().copied() }
+        } else {
+          quote! { ::core::iter::IntoIterator::into_iter([#( #cstrs ),*]) }
+        };
+        quote! {
+            let prefix = prefix.to_bytes();
+            ::core::iter::IntoIterator::into_iter([#( Self::#var_idents ),*]).zip(#names)
+                .filter(move |(_, name)| #krate::__private::starts_with(name.to_bytes(), prefix, #ignore_case, #any_separator))
+                .map(|(v, _)| v)
+        }
+      }
+    };
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Every variant together with its name, in declaration order.
+            pub fn cstr_variants() -> impl Iterator<Item = (Self, &'static ::core::ffi::CStr)> {
+                ::core::iter::IntoIterator::into_iter([#( (Self::#var_idents, #cstrs) ),*])
+            }
+
+            /// Every variant with a name starting with `prefix`, such as for completing a partially typed name.
+            ///
+            /// `prefix` is compared the same way as in a derived `from_cstr`.  The variants are in declaration
+            /// order, or in order of their names with `cstr(lookup = "binary_search")`.
+            pub fn variants_with_prefix(prefix: &::core::ffi::CStr) -> impl Iterator<Item = Self> + '_ {
+                #prefix_body
+            }
+
+            #slices_fn
+        }
+    }
+  } else {
+    quote! {}
+  };
+
+  // Accepts the same names as a derived `from_cstr`, but always returns the canonical name
+  let (str_names, str_arms): (Vec<_>, Vec<_>) = named.iter().filter(|v| !v.skip).flat_map(|v| {
+    let cstr = v.name.cstr(krate);
+    v.all_names(container.matching()).map(move |n| (n, quote! { Some(#cstr) }))
+  }).unzip();
+  let str_input = if container.trim.is_some() { quote! { #krate::__private::trim_ascii(s.as_bytes()) } } else { quote! { s.as_bytes() } };
+  let str_matcher = gen_matcher(krate, &str_input, &str_names, &str_arms, &quote! { None });
+
+  // A transparent name can't be borrowed from the field in a `const fn`
+  let is_fn = if named.len() == variants.len() && runtime_names.is_none() {
+    let bytes = variants.iter().map(|v| v.name.bytes(krate));
+    // Every name of each variant which can be parsed, owned by its index in `CSTR_VARIANTS`
+    let suggestions: Vec<_> = variants.iter().enumerate().filter(|(_, v)| !v.skip && v.other.is_none())
+      .flat_map(|(k, v)| v.all_names(container.matching()).map(move |(n, m)| (n, k, m.ignore_case)))
+      .collect();
+    let suggestion_lens = suggestions.iter().map(|(n, _, _)| n.len(krate));
+    let suggestions = suggestions.iter().map(|(n, k, ignore_case)| {
+      let bytes = n.bytes(krate);
+      quote! { (#bytes, #k, #ignore_case) }
+    });
+    let indices = 0..variants.len();
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Represent self as a `&'static CStr`, like `as_cstr`.
+            ///
+            /// Unlike `as_cstr`, this is a `const fn`, so it can be used to initialize constants and statics.
+            pub const fn as_cstr_const(&self) -> &'static ::core::ffi::CStr {
+                // Taken from `CSTR_VARIANTS`, so `name_fn` functions are only evaluated once
+                match *self {
+                    #( Self::#var_idents{..} => Self::CSTR_VARIANTS[#indices], )*
+                }
+            }
+
+            /// Check whether the name of self is exactly `name`, which excludes the nul terminator.
+            ///
+            /// Unlike `AsCStr::matches`, this is a `const fn`, so it can be used in `const` contexts.
+            pub const fn is(&self, name: &[u8]) -> bool {
+                match *self {
+                    #( Self::#var_idents{..} => #krate::__private::bytes_eq(#bytes, name), )*
+                }
+            }
+
+            /// The name closest to `s` by edit distance, such as for suggesting a name after a typo.
+            ///
+            /// `s` may be at most one edit away from the name for every three bytes, and at least one edit.  Names of
+            /// variants which `from_cstr` never gives are left out, and aliases lead to the name of their variant.
+            /// Returns `None` if no name is close enough, or if the closest names belong to different variants.
+            pub fn closest_name(s: &::core::ffi::CStr) -> Option<&'static ::core::ffi::CStr> {
+                let names: &[(&[u8], usize, bool)] = &[#( #suggestions ),*];
+                let mut row = [0usize; #krate::__private::max(&[#( #suggestion_lens ),*]) + 1];
+                let max_distance = (s.to_bytes().len() / 3).max(1);
+                #krate::__private::closest(s.to_bytes(), names, max_distance, &mut row).map(|k| Self::CSTR_VARIANTS[k])
+            }
+        }
+    }
+  } else {
+    quote! {}
+  };
+
+  let discriminant_fns = if container.emit_discriminant_conversions.is_some() {
+    if let Some(v) = variants.iter().find(|v| *v.fields != syn::Fields::Unit) {
+      return Error::new_spanned(v.fields, "variant cannot have fields when using `emit_discriminant_conversions`").to_compile_error().into();
+    }
+    // Converting from a discriminant or a name should give back every variant.  Names from `name_fn` or `cstr` are
+    // only known to the compiler, so this is checked when the constants are evaluated.
+    let round_trips = if input.generics.params.is_empty() && runtime_names.is_none() {
+      let bytes = named.iter().map(|v| v.name.bytes(krate));
+      let msgs = var_idents.iter().map(|v| format!("discriminant of `{}::{}` does not convert back to it", ident, v));
+      let msg = format!("names of `{}` must be distinct to convert back to every variant", ident);
+      quote! {
+          const _: () = {
+              #( assert!(matches!(#ident::try_from_i64(#ident::#var_idents as i64), Some(#ident::#var_idents)), #msgs); )*
+              assert!(#krate::__private::all_distinct(&[#( #bytes ),*]), #msg);
+          };
+      }
+    } else {
+      quote! {}
+    };
+    quote! {
+        #round_trips
+
+        impl #impl_generics From<#ident #ty_generics> for i64 #where_clause {
+            fn from(v: #ident #ty_generics) -> Self {
+                v as i64
+            }
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The variant with the discriminant `i`, the inverse of converting into an `i64`.
+            pub const fn try_from_i64(i: i64) -> Option<Self> {
+                #( if i == Self::#var_idents as i64 { return Some(Self::#var_idents); } )*
+                None
+            }
+        }
+    }
+  } else {
+    quote! {}
+  };
+
+  // Tags of a C tagged union, which are the discriminants of a `#[repr(u8)]` enum
+  let tag_fn = if has_repr_u8(&input.attrs) && named.len() == variants.len() && runtime_names.is_none()
+    && variants.iter().all(|v| *v.fields == syn::Fields::Unit) {
+    let body = match literal_tags(&input) {
+      // Contiguous tags index a table sorted by tag
+      Some(tags) if !tags.is_empty() && {
+        let mut sorted = tags.clone();
+        sorted.sort_unstable();
+        sorted.windows(2).all(|w| w[1] == w[0] + 1)
+      } => {
+        let mut by_tag: Vec<_> = tags.iter().zip(&cstrs).collect();
+        by_tag.sort_by_key(|(t, _)| **t);
+        let table = by_tag.iter().map(|(_, c)| c);
+        let n = tags.len();
+        let base = *by_tag[0].0 as usize;
+        quote! {
+            static NAMES: [&::core::ffi::CStr; #n] = [#( #table ),*];
+            (tag as usize).checked_sub(#base).and_then(|i| NAMES.get(i)).copied()
+        }
+      }
+      _ => quote! {
+          #( if tag == Self::#var_idents as u8 { return Some(#cstrs); } )*
+          None
+      },
+    };
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The name of the variant with the discriminant `tag`, such as the tag of a C tagged union.
+            pub fn name_for_tag(tag: u8) -> Option<&'static ::core::ffi::CStr> {
+                #body
+            }
+        }
+    }
+  } else {
+    quote! {}
+  };
+
+  if container.emit_c_module.is_some() && runtime_names.is_some() {
+    return runtime_error("emit_c_module");
+  }
+  if container.emit_lengths.is_some() && runtime_names.is_some() {
+    return runtime_error("emit_lengths");
+  }
+  let c_module = container.emit_c_module.as_ref().map(|module| {
+    let prefix = ident_to_snake_case(ident).to_string().trim_start_matches("r#").to_uppercase();
+    let count = syn::Ident::new(&format!("{}_COUNT", prefix), ident.span());
+    let names = syn::Ident::new(&format!("{}_NAMES", prefix), ident.span());
+    let n = named.len();
+    // Names may refer to items which aren't in scope inside the module
+    let indices = 0..n;
+    // Discriminants only exist if every variant is a unit variant
+    let discriminants = if variants.iter().all(|v| *v.fields == syn::Fields::Unit) {
+      let discriminants = syn::Ident::new(&format!("{}_DISCRIMINANTS", prefix), ident.span());
+      let doc = format!("The discriminant of each variant of [`{0}`](super::{0}), in the same order as `{1}`.", ident, names);
+      quote! {
+          #[doc = #doc]
+          #[no_mangle]
+          pub static #discriminants: [i64; #n] = [#( super::#ident::#var_idents as i64 ),*];
+      }
+    } else {
+      quote! {}
+    };
+    let module_doc = format!("Statics exporting the names of [`{0}`](super::{0}) to C.", ident);
+    let count_doc = format!("The number of variants of [`{0}`](super::{0}).", ident);
+    let names_doc = format!("The name of each variant of [`{0}`](super::{0}), in declaration order.", ident);
+    quote! {
+        #[doc = #module_doc]
+        pub mod #module {
+            #[doc = #count_doc]
+            #[no_mangle]
+            pub static #count: usize = #n;
+
+            #[doc = #names_doc]
+            #[no_mangle]
+            pub static #names: [#krate::CStrPtr; #n] = [#( #krate::CStrPtr::new(super::#ident::CSTR_VARIANTS[#indices]) ),*];
+
+            #discriminants
+        }
+    }
+  });
+
+  // Everything for generating C artifacts at once, with `to_c_header` below
+  let metadata = if container.emit_metadata.is_some() {
+    if let Some(v) = variants.iter().find(|v| *v.fields != syn::Fields::Unit) {
+      return Error::new_spanned(v.fields, "variant cannot have fields when using `emit_metadata`").to_compile_error().into();
+    }
+    if runtime_names.is_some() {
+      return runtime_error("emit_metadata");
+    }
+    let n = variants.len();
+    let indices = 0..n;
+    let ident_strs = variants.iter().map(|v| syn::LitStr::new(&v.ident.to_string(), v.ident.span()));
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// The identifier and name of every variant, in declaration order.
+            pub const CSTR_MAPPING: [(&'static str, &'static ::core::ffi::CStr); #n] = [#( (#ident_strs, Self::CSTR_VARIANTS[#indices]) ),*];
+
+            /// The discriminant of every variant, in the same order as `CSTR_VARIANTS`.
+            pub const CSTR_DISCRIMINANTS: [i128; #n] = [#( Self::#var_idents as i128 ),*];
+        }
+    }
+  } else {
+    quote! {}
+  };
+
+  let c_header_fn = if container.emit_c_header.is_some() || container.emit_metadata.is_some() {
+    if let Some(v) = variants.iter().find(|v| *v.fields != syn::Fields::Unit) {
+      return Error::new_spanned(v.fields, "variant cannot have fields when using `emit_c_header`").to_compile_error().into();
+    }
+    let ident_strs = variants.iter().map(|v| syn::LitStr::new(&v.ident.to_string(), v.ident.span()));
+    quote! {
+        #krate::__private::if_alloc! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// A C enum named `enum_name` mirroring this one, followed by a `{enum_name}_names` array with
+                /// the name of each variant.  Intended for generating headers from a build script.
+                pub fn to_c_header(enum_name: &str) -> #krate::__private::String {
+                    #krate::__private::c_header(
+                        enum_name,
+                        &[#( (#ident_strs, Self::#var_idents as i128) ),*],
+                        #names_list,
+                    )
+                }
+            }
+        }
+    }
+  } else {
+    quote! {}
+  };
+
+  if container.total_bytes_max.is_some() && runtime_names.is_some() {
+    return runtime_error("total_bytes_max");
   }
+  let budget_check = container.total_bytes_max.as_ref().map(|max| {
+    let lens = named.iter().map(|v| v.name.len(krate));
+    let msg = format!("the names of `{}` exceed `total_bytes_max`", ident);
+    quote_spanned! { max.span()=>
+        const _: () = assert!(0 #( + #lens + 1 )* <= #max, #msg);
+    }
+  });
+
+  // The names of transparent variants may not be UTF-8, and aren't known until runtime.  A byte string name
+  // which isn't UTF-8 is an error, unless the enum opts out with `no_string`.
+  let string_impl = if named.len() == variants.len() && container.no_string.is_none() {
+    // Only reported with the `alloc` feature, as the error is emitted in place of the impl
+    match variants.iter().map(|v| v.name.str(krate)).collect::<Result<Vec<_>>>() {
+      Ok(strs) => quote! {
+          impl #impl_generics From<&#ident #ty_generics> for #krate::__private::String #where_clause {
+              fn from(v: &#ident #ty_generics) -> Self {
+                  match *v {
+                      #( #ident::#var_idents{..} => #krate::__private::String::from(#strs), )*
+                  }
+              }
+          }
+      },
+      Err(e) => e.to_compile_error(),
+    }
+  } else {
+    quote! {}
+  };
+  // Foreign traits with a method shaped like `as_cstr`
+  let also_impls = container.also_impl.iter().map(|(trait_path, method)| quote_spanned! { trait_path.span()=>
+      impl #impl_generics #trait_path for #ident #ty_generics #where_clause {
+          fn #method(&self) -> &::core::ffi::CStr {
+              #krate::AsCStr::as_cstr(self)
+          }
+      }
+  });
 
+  let as_cstr_match = quote! {
+      match *self {
+          #( #as_cstr_arms, )*
+          #catchall_arm
+      }
+  };
+  // With `outline`, the match is kept out of callers so only a call is inlined
+  let style_warnings = style_warnings(&variants, &container);
+  let (as_cstr_inline, as_cstr_body, outlined_fn) = if container.outline.is_some() {
+    let outlined = quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc(hidden)]
+            #[inline(never)]
+            fn __cstr_enum_as_cstr(&self) -> &#as_cstr_lifetime ::core::ffi::CStr {
+                #as_cstr_match
+            }
+        }
+    };
+    (quote! { #[inline] }, quote! { self.__cstr_enum_as_cstr() }, outlined)
+  } else {
+    (quote! {}, as_cstr_match, quote! {})
+  };
+
+  let ts = quote! {
+       impl #impl_generics #krate::AsCStr for #ident #ty_generics #where_clause {
+            #as_cstr_inline
+            fn as_cstr(&self) -> &#as_cstr_lifetime ::core::ffi::CStr {
+                #as_cstr_body
+            }
+       }
+
+       #outlined_fn
+
+       #name_consts
+
+       #serialize_impl
+
+       #style_warnings
+
+       #( #also_impls )*
+
+       impl #impl_generics #ident #ty_generics #where_clause {
+            #names_const
+
+            /// The name of the variant which `s` names, if any, for passing Rust strings on to C.
+            ///
+            /// `s` is compared with the names the same way as in a derived `from_cstr`.
+            pub fn cstr_for_str(s: &str) -> Option<&'static ::core::ffi::CStr> {
+                #str_matcher
+            }
+       }
+
+       #variants_fn
+
+       #is_fn
+
+       #index_fns
+
+       #discriminant_fns
+       #tag_fn
+
+       #c_header_fn
+
+       #metadata
+
+       #c_module
+
+       #budget_check
+
+       #krate::__private::if_alloc! {
+            impl #impl_generics From<&#ident #ty_generics> for #krate::__private::CString #where_clause {
+                fn from(v: &#ident #ty_generics) -> Self {
+                    #krate::__private::CString::from(#krate::AsCStr::as_cstr(v))
+                }
+            }
+
+            #string_impl
+       }
+
+       #krate::__private::if_std! {
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// The value for the name of this variant in `map`, if any.
+                pub fn lookup_in<'m, V>(&self, map: &'m #krate::__private::HashMap<&'static ::core::ffi::CStr, V>) -> Option<&'m V> {
+                    map.get(#krate::AsCStr::as_cstr(self))
+                }
+            }
+       }
+
+       #krate::__private::if_assert_covers! {
+            #[cfg(test)]
+            impl #impl_generics #ident #ty_generics #where_clause {
+                /// Assert that the names of this enum are exactly `expected`, in any order.
+                ///
+                /// Intended for keeping bindings in sync with a C header.
+                pub fn assert_covers(expected: &[&::core::ffi::CStr]) {
+                    #krate::__private::assert_covers(#enum_name, #names_list, expected)
+                }
+            }
+       }
+    };
+
+  ts.into()
+}
+
+
+/// Derive macro for the [`FromCStr`] trait.  May only be applied to enums whose variants have no fields.
+#[proc_macro_derive(FromCStr, attributes(cstr))]
+pub fn derive_fromcstr_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+  let container = match ContainerMeta::from_attrs(&input.attrs) {
+    Ok(c) => c,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  let krate = &container.krate();
+
+  let variants = match get_name_mapping(&input, &container, "FromCStr", true) {
+    Ok(m) => m,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+
+  if let Some(CName::Runtime(path, _)) = variants.iter().map(|v| &v.name).find(|n| !n.is_const()) {
+    return Error::new_spanned(path, "`rename_with` names are computed at runtime, so they cannot be parsed by `FromCStr`").to_compile_error().into();
+  }
 
   let ident = &input.ident;
-  let error_msg = syn::LitStr::new(&format!("unexpected string while parsing for {} variant", ident), Span::call_site());
+  let name_consts = name_fn_consts(krate, &variants);
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+  let err_ty = match container.err_ty(krate) {
+    Ok(ty) => ty,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  // `bytes` is the input, in the body of `from_cstr_bytes`
+  let error = container.unknown_error(krate, ident, quote! { bytes });
+  let range_error = container.range_error(krate, ident);
+  let style_warnings = style_warnings(&variants, &container);
+
+  let fallback_variant = match get_fallback(&variants) {
+    Ok(f) => f,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  // The fallback is only ever the last resort, after every name has been compared
+  let miss = match &fallback_variant {
+    None => quote! { Err(#error) },
+    Some(Fallback::Unit(v)) => quote! { Ok(Self::#v) },
+    Some(Fallback::Capture(v)) => quote! { Ok(Self::#v(::core::convert::From::from(#krate::__private::cstring_until_nul(bytes)))) },
+  };
+
+  // The `other` variant catches everything which doesn't match, including its own name, and skipped variants
+  // are never parsed
+  let matched: Vec<_> = variants.iter().filter(|v| v.other.is_none() && !v.skip).collect();
+  let unparseable = if matched.is_empty() && fallback_variant.is_none() {
+    let msg = format!("`{}` has no variants to parse, so `from_cstr` always fails", ident);
+    match &container.deny_unparseable {
+      Some(path) => { return Error::new_spanned(path, msg).to_compile_error().into(); }
+      None => gen_warning(ident.span(), &msg),
+    }
+  } else {
+    quote! {}
+  };
+  let matching = container.matching();
+  let (names, arms): (Vec<_>, Vec<_>) = matched.iter().flat_map(|v| {
+    let var_ident = v.ident;
+    v.all_names(matching).map(move |n| (n, quote! { Ok(Self::#var_ident) }))
+  }).unzip();
+  if let Err(e) = check_unambiguous(&matched, matching) {
+    return e.to_compile_error().into();
+  }
+  let fallback = if container.prefix_fallback.is_some() {
+    // On a miss, look for the single variant with a name which the input is a prefix of
+    let conds = matched.iter().map(|v| {
+      let conds = v.all_names(matching).map(|(n, Matching { ignore_case, any_separator })| {
+        let val = n.bytes(krate);
+        quote! { #krate::__private::starts_with(#val, bytes, #ignore_case, #any_separator) }
+      });
+      quote! { #( #conds )||* }
+    });
+    let var_idents = matched.iter().map(|v| v.ident);
+    quote! { {
+        let mut found = None;
+        let mut count = 0usize;
+        #( if #conds {
+            found = Some(Ok(Self::#var_idents));
+            count += 1;
+        } )*
+        match found {
+            Some(v) if count == 1 => v,
+            _ => #miss,
+        }
+    } }
+  } else {
+    miss.clone()
+  };
+  let gen_lookup = |arms: &[TokenStream], fallback: &TokenStream| match &container.lookup {
+    Some((Lookup::BinarySearch, lit)) => gen_binary_search(krate, &quote! { bytes }, &names, arms, lit, fallback),
+    None => Ok(gen_matcher(krate, &quote! { bytes }, &names, arms, fallback)),
+  };
+  let matcher = match gen_lookup(&arms, &fallback) {
+    Ok(m) => m,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  // With `trim`, surrounding whitespace is dropped before anything else sees the input
+  let trim = container.trim.as_ref().map(|_| quote! { let bytes = #krate::__private::trim_ascii(bytes); });
+  let body = quote! {
+      #trim
+      #matcher
+  };
+  // A capturing `other` variant or `unknown_variant_error` keeps all of the input once it overflows the buffer
+  let keeps_overflow = match &fallback_variant {
+    Some(Fallback::Capture(_)) => true,
+    Some(Fallback::Unit(_)) => false,
+    None => container.unknown_variant_error.is_some(),
+  };
+  // Whitespace before the name, or after a name of the longest length, would otherwise fill the buffer.  Kept input
+  // can't drop whitespace in the middle of it, so there the overflow is trimmed as a whole instead.
+  let trim_skip = container.trim.as_ref().map(|_| if keeps_overflow {
+    quote! {
+        if b.is_ascii_whitespace() && len == 0 {
+            continue;
+        }
+    }
+  } else {
+    quote! {
+        if b.is_ascii_whitespace() && (len == 0 || len == Self::CSTR_NAME_MAX_LEN) {
+            continue;
+        }
+    }
+  });
+
+  // The same lookup again, for `from_cstr_detailed`.  Arms are in the order of `all_names`.
+  let detailed_arms: Vec<_> = matched.iter().flat_map(|v| {
+    let var_ident = v.ident;
+    // Aliases are numbered in the order they are written, without the ident of `accept_ident`
+    let kinds = v.aliases.iter().enumerate().map(move |(k, a)| if a.written {
+      let index = v.aliases[..k].iter().filter(|a| a.written).count();
+      quote! { Alias(#index) }
+    } else {
+      quote! { Ident }
+    });
+    std::iter::once(quote! { Canonical }).chain(kinds)
+      .map(move |kind| quote! { Ok((Self::#var_ident, #krate::MatchedName::#kind)) })
+  }).collect();
+  let detailed_fallback = quote! { (#fallback).map(|v| (v, #krate::MatchedName::Fallback)) };
+  let detailed_matcher = match gen_lookup(&detailed_arms, &detailed_fallback) {
+    Ok(m) => m,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+
+  let lens = names.iter().map(|(n, _)| n.len(krate));
+  // Input longer than any name is a miss, but a capturing `other` variant needs the rest of it
+  let overflow = match &fallback_variant {
+    // Trailing whitespace may still leave a name, so the whole input is parsed again
+    _ if keeps_overflow && container.trim.is_some() => quote! {
+        let mut bytes = #krate::__private::Vec::from(&buf[..len]);
+        bytes.push(b);
+        bytes.extend(iter.take_while(|&b| b != 0));
+        return Self::from_cstr_bytes(&bytes);
+    },
+    None if container.unknown_variant_error.is_some() => {
+      // The error keeps all of the input
+      let error = container.unknown_error(krate, ident, quote! { &bytes });
+      quote! {
+          let mut bytes = #krate::__private::Vec::from(&buf[..len]);
+          bytes.push(b);
+          bytes.extend(iter.take_while(|&b| b != 0));
+          return Err(#error);
+      }
+    },
+    None => {
+      // The input is longer than any name, so it isn't empty, but only the start of it is kept
+      let error = container.unknown_error(krate, ident, quote! { [b] });
+      quote! { return Err(#error); }
+    },
+    Some(Fallback::Unit(v)) => quote! { return Ok(Self::#v); },
+    Some(Fallback::Capture(v)) => quote! {
+        let mut bytes = #krate::__private::Vec::from(&buf[..len]);
+        bytes.push(b);
+        bytes.extend(iter.take_while(|&b| b != 0));
+        // the bytes stop before the first nul
+        return Ok(Self::#v(::core::convert::From::from(unsafe { #krate::__private::CString::from_vec_unchecked(bytes) })));
+    },
+  };
+  let max_len = quote! { #krate::__private::max(&[#( #lens ),*]) };
+  // Every name of each variant, for `from_cstr_fuzzy`
+  let fuzzy_names = matched.iter().enumerate().flat_map(|(k, v)| {
+    v.all_names(matching).map(move |(n, Matching { ignore_case, .. })| {
+      let bytes = n.bytes(krate);
+      quote! { (#bytes, #k, #ignore_case) }
+    })
+  });
+  let fuzzy_indices = 0..matched.len();
+  let fuzzy_idents = matched.iter().map(|v| v.ident);
+  let mut try_from_generics = input.generics.clone();
+  try_from_generics.params.insert(0, syn::parse_quote! { '__cstr_enum_s });
+  let (try_from_impl_generics, _, _) = try_from_generics.split_for_impl();
+  let mut de_generics = input.generics.clone();
+  de_generics.params.insert(0, syn::parse_quote! { '__cstr_enum_de });
+  let (de_impl_generics, _, _) = de_generics.split_for_impl();
+  let enum_name = syn::LitStr::new(&ident.to_string(), Span::call_site());
+  // Opt-in, so that enums can still implement `Deserialize` themselves when the feature is enabled
+  let deserialize_impl = container.serde.as_ref().map(|_| quote! {
+      #krate::__private::if_serde! {
+          impl #de_impl_generics #krate::__private::serde::Deserialize<'__cstr_enum_de> for #ident #ty_generics #where_clause {
+              fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+              where
+                  D: #krate::__private::serde::Deserializer<'__cstr_enum_de>,
+              {
+                  let visitor = #krate::__private::NameVisitor {
+                      enum_name: #enum_name,
+                      // The `other` variant would otherwise capture the name up to a nul byte
+                      parse: |bytes| if bytes.contains(&0) {
+                          None
+                      } else {
+                          Self::from_cstr_bytes(bytes).ok()
+                      },
+                  };
+                  // Binary formats may hold names which aren't UTF-8
+                  if deserializer.is_human_readable() {
+                      deserializer.deserialize_str(visitor)
+                  } else {
+                      deserializer.deserialize_bytes(visitor)
+                  }
+              }
+          }
+      }
+  });
 
   let ts = quote! {
-       impl cstr_enum::FromCStr for #ident {
-            type Err = &'static str;
-            fn from_cstr(s: &std::ffi::CStr) -> Result<Self, Self::Err> {
-                match s.to_bytes() {
-                    #( #vals => Ok(Self::#var_idents), )*
-                    _ => Err(#error_msg)
+       impl #impl_generics #krate::FromCStr for #ident #ty_generics #where_clause {
+            type Err = #err_ty;
+            fn from_cstr(s: &::core::ffi::CStr) -> Result<Self, Self::Err> {
+                Self::from_cstr_bytes(s.to_bytes())
+            }
+       }
+
+       impl #try_from_impl_generics ::core::convert::TryFrom<&'__cstr_enum_s ::core::ffi::CStr> for #ident #ty_generics #where_clause {
+            type Error = <Self as #krate::FromCStr>::Err;
+            fn try_from(s: &'__cstr_enum_s ::core::ffi::CStr) -> Result<Self, Self::Error> {
+                <Self as #krate::FromCStr>::from_cstr(s)
+            }
+       }
+
+       impl #impl_generics #ident #ty_generics #where_clause {
+            /// The length in bytes of the longest name, excluding the nul terminator.
+            pub const CSTR_NAME_MAX_LEN: usize = #max_len;
+
+            /// Parse a name given as bytes, which must not include the nul terminator.
+            ///
+            /// This is the same as `from_cstr`, without needing to build a `CStr` first.  Input containing a nul byte
+            /// never matches a name, though the `other` variant only keeps the bytes before the first nul.
+            pub fn from_cstr_bytes(bytes: &[u8]) -> Result<Self, <Self as #krate::FromCStr>::Err> {
+                #body
+            }
+
+            /// Parse like `from_cstr`, and also return which of the variant's names matched.
+            pub fn from_cstr_detailed(s: &::core::ffi::CStr) -> Result<(Self, #krate::MatchedName), <Self as #krate::FromCStr>::Err> {
+                let bytes = s.to_bytes();
+                #trim
+                #detailed_matcher
+            }
+
+            /// Find the variant with the name closest to `s`, within `max_distance` single-byte insertions, deletions or
+            /// substitutions.
+            ///
+            /// This is not an exact parse: it is meant for forgiving interactive input, such as accepting typos.  The
+            /// aliases of a variant count as its names, but there is no fallback to the `default` or `other` variants.
+            /// Returns `None` if no name is close enough, or if the closest names belong to different variants.
+            pub fn from_cstr_fuzzy(s: &::core::ffi::CStr, max_distance: usize) -> Option<Self> {
+                let names: &[(&[u8], usize, bool)] = &[#( #fuzzy_names ),*];
+                let mut row = [0usize; #max_len + 1];
+                match #krate::__private::closest(s.to_bytes(), names, max_distance, &mut row) {
+                    #( Some(#fuzzy_indices) => Some(Self::#fuzzy_idents), )*
+                    _ => None,
+                }
+            }
+
+            /// Parse the bytes yielded by `iter`, up to the first nul byte or the end of the iterator.
+            ///
+            /// The bytes are collected into a stack buffer of `CSTR_NAME_MAX_LEN + 1` bytes, so no allocation
+            /// is performed.  Input longer than `CSTR_NAME_MAX_LEN` cannot match any name, so it is treated
+            /// as a miss as soon as the buffer is full.  The rest of the iterator is only consumed if it is captured
+            /// by an `other` variant, or by the error with `cstr(unknown_variant_error)`.
+            pub fn from_byte_iter<I: IntoIterator<Item = u8>>(iter: I) -> Result<Self, <Self as #krate::FromCStr>::Err> {
+                let mut buf = [0u8; #max_len + 1];
+                let mut len = 0;
+                let mut iter = iter.into_iter();
+                while let Some(b) = iter.next() {
+                    if b == 0 {
+                        break;
+                    }
+                    #trim_skip
+                    if len == Self::CSTR_NAME_MAX_LEN {
+                        #overflow
+                    }
+                    buf[len] = b;
+                    len += 1;
                 }
+                Self::from_cstr_bytes(&buf[..len])
+            }
+
+            /// Parse the field of `len` bytes at `start` in `buf`, such as a fixed-size name in a packed record.
+            ///
+            /// The field ends at its first nul byte, if any, so nul padding is ignored.  A range which is out of
+            /// bounds of `buf` is an error, distinct from the one for an unknown name unless `cstr(unit_error)`
+            /// or `cstr(error)` is used.
+            pub fn from_cstr_field(buf: &[u8], start: usize, len: usize) -> Result<Self, <Self as #krate::FromCStr>::Err> {
+                match start.checked_add(len).and_then(|end| buf.get(start..end)) {
+                    Some(field) => Self::from_byte_iter(field.iter().copied()),
+                    None => Err(#range_error),
+                }
+            }
+
+            /// Parse a length-prefixed (Pascal-style) name, of `buf[0]` bytes following the length byte.
+            ///
+            /// Like `from_cstr_field`, the name ends at its first nul byte, if any.  A length which is longer than
+            /// the rest of `buf`, or an empty `buf`, is an out of bounds error.
+            pub fn from_pascal_bytes(buf: &[u8]) -> Result<Self, <Self as #krate::FromCStr>::Err> {
+                // An empty `buf` has no length, and no room for the name either
+                let len = buf.first().map_or(0, |&len| len as usize);
+                Self::from_cstr_field(buf, 1, len)
             }
        }
+
+       #deserialize_impl
+
+       #unparseable
+       #style_warnings
+       #name_consts
     };
 
   ts.into()
 }
 
+
+/// Derive macro generating a table of handlers keyed by the C string names of an enum's variants.
+/// May only be applied to enums with unit variants.
+#[proc_macro_derive(CStrDispatch, attributes(cstr))]
+pub fn derive_cstrdispatch_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+  let container = match ContainerMeta::from_attrs(&input.attrs) {
+    Ok(c) => c,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  let krate = &container.krate();
+
+  let variants = match get_name_mapping(&input, &container, "CStrDispatch", true) {
+    Ok(m) => m,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+  let vis = &input.vis;
+  let handlers_ident = syn::Ident::new(&format!("{}Handlers", ident), ident.span());
+  let fields: Vec<_> = variants.iter().map(|v| ident_to_snake_case(v.ident)).collect();
+  let field_docs = variants.iter().map(|v| format!("Handler for [`{}::{}`].", ident, v.ident));
+  let handlers_doc = format!("One handler per variant of [`{}`], called by [`{}::dispatch`].", ident, ident);
+
+  let matching = container.matching();
+  if let Err(e) = check_unambiguous(&variants.iter().collect::<Vec<_>>(), matching) {
+    return e.to_compile_error().into();
+  }
+  let (names, arms): (Vec<_>, Vec<_>) = variants.iter().zip(&fields).flat_map(|(v, f)| {
+    v.all_names(matching).map(move |n| (n, quote! { Some((handlers.#f)()) }))
+  }).unzip();
+  let matcher = gen_matcher(krate, &quote! { s.to_bytes() }, &names, &arms, &quote! { None });
+  // Without any handlers, `R` would be unused
+  let marker = if variants.is_empty() {
+    quote! {
+        /// There are no variants to handle, so this only holds the return type.
+        pub returns: ::core::marker::PhantomData<fn() -> R>,
+    }
+  } else {
+    quote! {}
+  };
+
+  let name_consts = name_fn_consts(krate, &variants);
+  let ts = quote! {
+       #name_consts
+
+       #[doc = #handlers_doc]
+       #vis struct #handlers_ident<R> {
+            #(
+                #[doc = #field_docs]
+                pub #fields: fn() -> R,
+            )*
+            #marker
+       }
+
+       impl #impl_generics #ident #ty_generics #where_clause {
+            /// Call the handler for the variant named `s`, returning `None` if there is no such variant.
+            pub fn dispatch<R>(s: &::core::ffi::CStr, handlers: &#handlers_ident<R>) -> Option<R> {
+                #matcher
+            }
+       }
+    };
+
+  ts.into()
+}
+
+
+/// Derive macro implementing `Debug` by printing the [`AsCStr`] name, escaping bytes which are not printable ASCII.
+#[proc_macro_derive(CStrDebugEscaped, attributes(cstr))]
+pub fn derive_cstrdebugescaped_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+  let container = match ContainerMeta::from_attrs(&input.attrs) {
+    Ok(c) => c,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  let krate = &container.krate();
+
+  if !matches!(input.data, syn::Data::Enum(_)) {
+    return Error::new(Span::call_site(), "target must be an enum").to_compile_error().into();
+  }
+
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  let ts = quote! {
+       impl #impl_generics ::core::fmt::Debug for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #krate::__private::fmt_escaped(#krate::AsCStr::as_cstr(self).to_bytes(), f)
+            }
+       }
+    };
+
+  ts.into()
+}
+
+
+/// Derive macro for the [`AsCStrIn`] trait.  May only be applied to enums.
+#[proc_macro_derive(AsCStrIn, attributes(cstr))]
+pub fn derive_ascstrin_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+  let container = match ContainerMeta::from_attrs(&input.attrs) {
+    Ok(c) => c,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  let krate = &container.krate();
+
+  let variants = match get_name_mapping(&input, &container, "AsCStrIn", false) {
+    Ok(m) => m,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+
+  let ident = &input.ident;
+  let name_consts = name_fn_consts(krate, &variants);
+  let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+  let mut generics = input.generics.clone();
+  generics.params.insert(0, syn::parse_quote! { '__cstr_enum_arena });
+  let (impl_generics, _, _) = generics.split_for_impl();
+
+  // Static names are returned as they are, while the others are copied into the arena
+  let arms = variants.iter().map(|v| {
+    let var_ident = v.ident;
+    if let Some(path) = &v.name_in {
+      let krate = respan(krate, path.span());
+      quote_spanned! { path.span()=>
+          Self::#var_ident{..} => #krate::__private::cstr_in(arena, ::core::convert::AsRef::<[u8]>::as_ref(&#path(self)))
+      }
+    } else if v.transparent.is_some() {
+      quote! {
+          Self::#var_ident(ref name) => {
+              let name = ::core::convert::AsRef::<::core::ffi::CStr>::as_ref(name);
+              #krate::__private::cstr_in(arena, name.to_bytes())
+          }
+      }
+    } else {
+      let cstr = v.name.cstr(krate);
+      quote! { Self::#var_ident{..} => #cstr }
+    }
+  });
+
+  let ts = quote! {
+       impl #impl_generics #krate::AsCStrIn<'__cstr_enum_arena> for #ident #ty_generics #where_clause {
+            fn as_cstr_in(&self, arena: &'__cstr_enum_arena #krate::__private::Bump) -> &'__cstr_enum_arena ::core::ffi::CStr {
+                match *self {
+                    #( #arms, )*
+                }
+            }
+       }
+
+       #name_consts
+    };
+
+  ts.into()
+}
+
+
+/// Derive macro implementing `Display` by printing the [`AsCStr`] name, replacing invalid UTF-8 with `U+FFFD`.
+#[proc_macro_derive(CStrDisplay, attributes(cstr))]
+pub fn derive_cstrdisplay_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+  let container = match ContainerMeta::from_attrs(&input.attrs) {
+    Ok(c) => c,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  let krate = &container.krate();
+
+  if !matches!(input.data, syn::Data::Enum(_)) {
+    return Error::new(Span::call_site(), "target must be an enum").to_compile_error().into();
+  }
+
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+  let ts = quote! {
+       impl #impl_generics ::core::fmt::Display for #ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                #krate::__private::fmt_lossy(#krate::AsCStr::as_cstr(self).to_bytes(), f)
+            }
+       }
+    };
+
+  ts.into()
+}
+
+
+/// Derive macro for the [`AsCWStr`] trait.  May only be applied to enums.
+#[proc_macro_derive(AsCWStr, attributes(cstr))]
+pub fn derive_ascwstr_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+  let container = match ContainerMeta::from_attrs(&input.attrs) {
+    Ok(c) => c,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  let krate = &container.krate();
+
+  let variants = match get_name_mapping(&input, &container, "AsCWStr", false) {
+    Ok(m) => m,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+
+  // Only static names can be encoded, so variants whose name is held in a field have none
+  for v in &variants {
+    if let Some(path) = &v.transparent {
+      return Error::new_spanned(path, "`transparent` variants cannot be used with wide strings").to_compile_error().into();
+    }
+    if let (Some(path), false) = (&v.other, v.fields == &syn::Fields::Unit) {
+      return Error::new_spanned(path, "capturing `other` variants cannot be used with wide strings").to_compile_error().into();
+    }
+  }
+
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+  let var_idents = variants.iter().map(|v| v.ident);
+  let wides = match variants.iter().map(|v| v.name.wide()).collect::<Result<Vec<_>>>() {
+    Ok(w) => w,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+
+  let ts = quote! {
+       impl #impl_generics #krate::AsCWStr for #ident #ty_generics #where_clause {
+            fn as_cwstr(&self) -> &'static [u16] {
+                match *self {
+                    #( Self::#var_idents{..} => &[#( #wides, )* 0], )*
+                }
+            }
+       }
+    };
+
+  ts.into()
+}
+
+/// Derive macro for the [`FromCWStr`] trait.  May only be applied to enums with unit variants.
+#[proc_macro_derive(FromCWStr, attributes(cstr))]
+pub fn derive_fromcwstr_enum(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+  let input = syn::parse_macro_input!(input as syn::DeriveInput);
+
+  let container = match ContainerMeta::from_attrs(&input.attrs) {
+    Ok(c) => c,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  let krate = &container.krate();
+
+  let variants = match get_name_mapping(&input, &container, "FromCWStr", true) {
+    Ok(m) => m,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+
+  if let Some(path) = &container.prefix_fallback {
+    return Error::new_spanned(path, "`prefix_fallback` cannot be used with wide strings").to_compile_error().into();
+  }
+
+  let ident = &input.ident;
+  let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+  let err_ty = match container.err_ty(krate) {
+    Ok(ty) => ty,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  if let Some(path) = &container.unknown_variant_error {
+    return Error::new_spanned(path, "`unknown_variant_error` cannot be used with wide strings").to_compile_error().into();
+  }
+  let error = container.unknown_error(krate, ident, quote! { s });
+  let miss = match get_fallback(&variants) {
+    Ok(None) => quote! { Err(#error) },
+    Ok(Some(Fallback::Unit(v))) => quote! { Ok(Self::#v) },
+    Ok(Some(Fallback::Capture(v))) => {
+      return Error::new_spanned(v, "`other` variant cannot have a field with wide strings").to_compile_error().into();
+    }
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+
+  let matched: Vec<_> = variants.iter().filter(|v| v.other.is_none() && !v.skip).collect();
+  let matching = container.matching();
+  if let Err(e) = check_unambiguous(&matched, matching) {
+    return e.to_compile_error().into();
+  }
+  let var_idents = matched.iter().flat_map(|v| v.all_names(matching).map(move |_| v.ident));
+  let names: Vec<_> = matched.iter().flat_map(|v| v.all_names(matching)).collect();
+  let wides = match names.iter().map(|(n, _)| n.wide()).collect::<Result<Vec<_>>>() {
+    Ok(w) => w,
+    Err(e) => { return e.to_compile_error().into(); }
+  };
+  let body = if names.iter().all(|(_, m)| m.is_exact()) {
+    quote! {
+        match s {
+            #( [#( #wides ),*] => Ok(Self::#var_idents), )*
+            _ => #miss,
+        }
+    }
+  } else {
+    let ignore_case = names.iter().map(|(_, m)| m.ignore_case);
+    let any_separator = names.iter().map(|(_, m)| m.any_separator);
+    quote! {
+        #( if #krate::__private::eq_wide(s, &[#( #wides ),*], #ignore_case, #any_separator) { Ok(Self::#var_idents) } else )*
+        { #miss }
+    }
+  };
+
+  let ts = quote! {
+       impl #impl_generics #krate::FromCWStr for #ident #ty_generics #where_clause {
+            type Err = #err_ty;
+            fn from_cwstr(s: &[u16]) -> Result<Self, Self::Err> {
+                let s = #krate::__private::wide_until_nul(s);
+                #body
+            }
+       }
+    };
+
+  ts.into()
+}
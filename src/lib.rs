@@ -1,4 +1,5 @@
 #![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 //! A crate for defining C-style string enums.
 //!
 //! C APIs sometimes require string constants.  One could define a bunch of `&CStr` constants using the
@@ -26,6 +27,7 @@
 //! assert_eq!(Constants::from_cstr(returned_from_c_api), Ok(Constants::Cat));
 //!
 //! let returned_from_c_api = CStr::from_bytes_with_nul(b"unknown\0").unwrap();
+//! # #[cfg(not(feature = "rich_error"))]
 //! assert_eq!(
 //!   Constants::from_cstr(returned_from_c_api),
 //!   Err("unexpected string while parsing for Constants variant")
@@ -42,7 +44,92 @@
 //!
 //! assert_eq!(Constants::Bacon.as_cstr().to_bytes_with_nul(), b"pork\0");
 //! ```
-//! Nul bytes in the supplied string will be rejected at compile time.
+//! With the `cstr(accept_ident)` flag on a renamed variant, or on the enum to apply it to every variant, `from_cstr`
+//! also accepts the variant's identifier.  This helps when migrating to new names.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! enum Constants {
+//!   #[cstr(name="pork", accept_ident)]
+//!   Bacon,
+//! }
+//!
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"Bacon\0").unwrap();
+//! assert_eq!(Constants::from_cstr(returned_from_c_api), Ok(Constants::Bacon));
+//! assert_eq!(Constants::Bacon.as_cstr().to_bytes(), b"pork");
+//! ```
+//! Other spellings to accept can be given with `cstr(alias = "...")`, which may be repeated.  Like `accept_ident`, aliases
+//! are only parsed, and `as_cstr` always returns the variant's own name.  An alias which collides with a name or alias
+//! of another variant is a compile error.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! enum Setting {
+//!   #[cstr(name="color", alias="colour")]
+//!   Color,
+//! }
+//!
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"colour\0").unwrap();
+//! assert_eq!(Setting::from_cstr(returned_from_c_api), Ok(Setting::Color));
+//! assert_eq!(Setting::Color.as_cstr().to_bytes(), b"color");
+//! ```
+//! The longer form `cstr(alias(value = "..."))` also takes flags.  Adding `case_insensitive` to an alias ignores ASCII
+//! case for that name only, while the variant's own name is still compared the way the enum specifies.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! enum Method {
+//!   #[cstr(name="GET", alias(value="get", case_insensitive))]
+//!   Get,
+//!   #[cstr(name="POST")]
+//!   Post,
+//! }
+//!
+//! let cstr = |b| CStr::from_bytes_with_nul(b).unwrap();
+//! assert_eq!(Method::from_cstr(cstr(b"Get\0")), Ok(Method::Get));
+//! assert!(Method::from_cstr(cstr(b"Post\0")).is_err());
+//! assert_eq!(Method::Get.as_cstr().to_bytes(), b"GET");
+//! ```
+//! To tell which name was used, such as to warn about a deprecated spelling, the derived `from_cstr_detailed` parses
+//! like `from_cstr` and also returns a [`MatchedName`].
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! enum Setting {
+//!   #[cstr(name="color", alias="colour", alias="colr")]
+//!   Color,
+//! }
+//!
+//! let cstr = |b| CStr::from_bytes_with_nul(b).unwrap();
+//! assert_eq!(Setting::from_cstr_detailed(cstr(b"color\0")), Ok((Setting::Color, MatchedName::Canonical)));
+//! assert_eq!(Setting::from_cstr_detailed(cstr(b"colr\0")), Ok((Setting::Color, MatchedName::Alias(1))));
+//! ```
+//! `cstr` is a helper attribute of the derive macros rather than an attribute macro, so it must be written
+//! unqualified.  The compiler rejects qualified paths such as `#[cstr_enum::cstr(...)]` before the derive macros are
+//! run.
+//!
+//! The generated code refers to this crate by the absolute path `::cstr_enum`, so it works wherever the enum is
+//! defined or used.  If `cstr_enum` is renamed in `Cargo.toml`, or only available through a re-export from another
+//! crate, give its path with `cstr(crate = "path::to::cstr_enum")` on the enum.  This includes enums defined by a
+//! `macro_rules!` macro for crates which may not depend on `cstr_enum` directly.  Since `$crate` is not expanded
+//! inside string literals, such macros should re-export `cstr_enum` and name it by an absolute path, such as
+//! `cstr(crate = "::my_crate::cstr_enum")`.
+//! ```
+//! # extern crate cstr_enum as renamed;
+//! # use renamed::*;
+//! #[derive(AsCStr)]
+//! #[cstr(crate = "renamed")]
+//! enum Constants {
+//!   Bacon,
+//! }
+//! ```
+//!
+//! The supplied string may end with a nul terminator, as in `cstr(name="pork\0")`, which is then not added a second
+//! time.  Any other nul bytes in the supplied string will be rejected at compile time.
 //! ```compile_fail
 //! # use cstr_enum::*;
 //! #
@@ -57,6 +144,145 @@
 //!   |   #[cstr(name="p\0rk")]
 //!   |               ^^^^^^^
 //! ```
+//! Names which aren't valid UTF-8 can be given as byte strings, as in `cstr(name=b"\xFFraw")`.  These are taken as is,
+//! so may not contain any nul bytes.  Such a name has no `String` to convert into, so an enum using one needs the
+//! `cstr(no_string)` flag, which skips generating `From<&Enum> for String`.
+//!
+//! Names can also be computed by a `const fn() -> &'static [u8]`, given by path with `cstr(name_fn="path::to::function")`.
+//! The function is called at compile time and must return nul-terminated bytes with no interior nul bytes, otherwise
+//! compilation fails.
+//! ```
+//! # use cstr_enum::*;
+//! const fn pork() -> &'static [u8] {
+//!   b"pork\0"
+//! }
+//!
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! enum Constants {
+//!   #[cstr(name_fn="pork")]
+//!   Bacon,
+//! }
+//!
+//! assert_eq!(Constants::Bacon.as_cstr().to_bytes_with_nul(), b"pork\0");
+//! ```
+//! An existing `&'static CStr` constant can be reused as a name with `cstr(cstr="path::to::CONSTANT")`.  `as_cstr`
+//! returns the constant itself.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! const PORK: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"pork\0") };
+//!
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! enum Constants {
+//!   #[cstr(cstr="PORK")]
+//!   Bacon,
+//! }
+//!
+//! assert_eq!(Constants::Bacon.as_cstr(), PORK);
+//! assert_eq!(Constants::from_cstr(PORK), Ok(Constants::Bacon));
+//! ```
+//! For C APIs with numbered constants, `cstr(name_template="...")` on the enum names each variant by replacing the `{}`
+//! in the template with its position in declaration order.  Variants with their own `name` keep it.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! #[cstr(name_template="SLOT_{}")]
+//! enum Slot {
+//!   First,
+//!   Second,
+//!   #[cstr(name="SLOT_LAST")]
+//!   Last,
+//! }
+//!
+//! assert_eq!(Slot::Second.as_cstr().to_bytes(), b"SLOT_1");
+//! assert_eq!(Slot::Last.as_cstr().to_bytes(), b"SLOT_LAST");
+//! ```
+//! Enums with hundreds of such variants can add the `cstr(concat_names)` flag, which stores every name in a single
+//! buffer, so `as_cstr` only looks up an index into one table of names rather than having a separate constant for
+//! each variant.  This saves binary size from the per-name overhead, and requires every name to be a literal.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[cstr(name_template="REG{}", concat_names)]
+//! enum Register {
+//!   R0,
+//!   R1,
+//!   R2,
+//! }
+//!
+//! assert_eq!(Register::R1.as_cstr().to_bytes(), b"REG1");
+//! ```
+//! The identifiers of variants without their own `name` can instead be converted to another casing with
+//! `cstr(rename_all="...")` on the enum, which is one of `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"`,
+//! `"camelCase"` or `"lowercase"`.  Identifiers are split into words at underscores and before capital letters, keeping
+//! runs of capitals together, so `HTTPServer` becomes `HTTP_SERVER`, `http-server` or `httpServer`.  Acronyms
+//! listed with `cstr(acronyms("...", ...))` are split off as words of their own and are always written in uppercase,
+//! even in lowercase styles.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[cstr(rename_all="snake_case", acronyms("HTTP", "URL"))]
+//! enum Request {
+//!   HttpUrl,
+//!   HttpServerError,
+//! }
+//!
+//! assert_eq!(Request::HttpUrl.as_cstr().to_bytes(), b"HTTP_URL");
+//! assert_eq!(Request::HttpServerError.as_cstr().to_bytes(), b"HTTP_server_error");
+//! ```
+//! Variants with their own `name` keep it as written.  In large sets of bindings, the `cstr(warn_style)` flag
+//! next to `rename_all` catches overrides which are probably a mistake, with a warning for each `name` that
+//! `rename_all` would have written differently, such as `"NotSnake"` under `"snake_case"`.
+//!
+//! C libraries often start every constant with the same prefix, which `cstr(prefix="...")` on the enum adds to each
+//! name, and likewise `cstr(suffix="...")` at the end.  They go around the name however it was found, whether from the
+//! identifier, `rename_all`, `name_template` or the variant's own `name`, and around its aliases too.  A variant marked
+//! `cstr(verbatim)` keeps its names exactly as written.  Names from `name_fn`, `cstr` or `rename_with` are never known
+//! by the derive macro, so they are always used as they are.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[cstr(prefix="GL_", rename_all="SCREAMING_SNAKE_CASE")]
+//! enum Primitive {
+//!   Triangles,
+//!   #[cstr(name="LINE_STRIP")]
+//!   LineStrip,
+//!   #[cstr(name="NONE", verbatim)]
+//!   Empty,
+//! }
+//!
+//! assert_eq!(Primitive::Triangles.as_cstr().to_bytes(), b"GL_TRIANGLES");
+//! assert_eq!(Primitive::LineStrip.as_cstr().to_bytes(), b"GL_LINE_STRIP");
+//! assert_eq!(Primitive::Empty.as_cstr().to_bytes(), b"NONE");
+//! ```
+//! For casing schemes which the attributes above don't cover, `cstr(rename_with="path::to::function")` on the enum names
+//! each variant by calling a function with its identifier, returning a `String` or `&'static str`.  Derive macros can't
+//! call user functions, so unlike every other way of naming variants, this happens at runtime: the first `as_cstr` of
+//! each variant calls the function and caches the name in a static, so later calls only pay for a check of the cache.
+//! The names are not known at compile time, so there is no `CSTR_VARIANTS` constant or `is` function, and `FromCStr`,
+//! `emit_c_module` and `total_bytes_max` cannot be used.  Variants with their own `name` keep it.  This requires the
+//! `std` feature.
+//! ```
+//! # use cstr_enum::*;
+//! fn screaming_kebab_case(ident: &str) -> String {
+//!   ident.chars().enumerate().fold(String::new(), |mut name, (i, c)| {
+//!     if i > 0 && c.is_uppercase() {
+//!       name.push('-');
+//!     }
+//!     name.push(c.to_ascii_uppercase());
+//!     name
+//!   })
+//! }
+//!
+//! #[derive(AsCStr)]
+//! #[cstr(rename_with="screaming_kebab_case")]
+//! enum Constants {
+//!   MaxRetries,
+//! }
+//!
+//! # #[cfg(feature = "std")]
+//! assert_eq!(Constants::MaxRetries.as_cstr().to_bytes(), b"MAX-RETRIES");
+//! ```
 //! When deriving `AsCStr`, enum variants may contain fields:
 //! ```
 //! # use cstr_enum::*;
@@ -84,32 +310,2045 @@
 //!   |   Foo{ bar: u8 },
 //!   |   ^^^^^^^^^^^^^^
 //! ```
+//! A variant with a single field holding its own name, such as a `&CStr` or `CString`, can be marked
+//! `cstr(transparent)` so that `as_cstr` returns the field.  The name then borrows from the enum, rather than being
+//! `'static` as it otherwise would be, and the variant is left out of `CSTR_VARIANTS`.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(AsCStr)]
+//! enum Constants<'a> {
+//!   #[cstr(transparent)]
+//!   Borrowed(&'a CStr),
+//!   Named,
+//! }
 //!
-//! Conversion between Rust strings ([`str`] and [`String`]) is not supported by this crate. Instead, check out
-//! the [`strum`](https://docs.rs/strum/) crate.
-use std::ffi::CStr;
-
-/// Conversion to a C-style string.
-///
-/// If using the derive macro, this will be a cheap conversion.
-pub trait AsCStr {
-  /// Represent self as a [`&CStr`](std::ffi::CStr).
-  fn as_cstr(&self) -> &CStr;
-}
-
-/// Conversion from a C-style string
-///
-/// This trait should be used the same way as [`std::str::FromStr`], although
-/// analogous `.parse()` method is not provided `&CStr`
-pub trait FromCStr {
-  /// The error type returned if parsing fails.
-  ///
-  /// If using the derive macro, this will be `&'static str`.
-  type Err : Sized;
-  /// Parse the `&CStr` for an instance of `Self`.
-  ///
-  /// If using the derive macro, this will be a `match` statement over `&'static [u8]`.
-  fn from_cstr(s: &CStr) -> Result<Self, Self::Err> where Self: Sized;
-}
-
-pub use cstr_enum_derive::*;
+//! let held = CStr::from_bytes_with_nul(b"held\0").unwrap();
+//! assert_eq!(Constants::Borrowed(held).as_cstr(), held);
+//! assert_eq!(Constants::Named.as_cstr().to_bytes_with_nul(), b"Named\0");
+//! ```
+//! Variants disabled by a `#[cfg(...)]` attribute are removed by the compiler before the derive macros run, so they
+//! are left out of every generated item, such as `CSTR_VARIANTS` and the positions given by `emit_index`.
+//!
+//! For `#[non_exhaustive]` enums, `cstr(as_cstr_catchall="...")` on the enum adds a `_` arm returning the given name to
+//! `as_cstr`, so the implementation stays the same shape as variants are added.  The name is not part of
+//! `CSTR_VARIANTS`.  Every variant is still matched by name, so this is an error on exhaustive enums.
+//!
+//! The `cstr(normalize="...")` attribute on the enum itself applies an ASCII case transformation to every name,
+//! and `from_cstr` compares its input under the same transformation.  The supported values are `"ascii_lowercase"`
+//! and `"ascii_uppercase"`.  This guarantees that every name produced by `as_cstr` parses back to the same variant,
+//! while also accepting inputs which are not in normal form.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! #[cstr(normalize="ascii_lowercase")]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(name="BACON")]
+//!   Bacon,
+//! }
+//!
+//! assert_eq!(Constants::Bacon.as_cstr().to_bytes_with_nul(), b"bacon\0");
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"APple\0").unwrap();
+//! assert_eq!(Constants::from_cstr(returned_from_c_api), Ok(Constants::Apple));
+//! ```
+//!
+//! Instead of returning an error, `from_cstr` can fall back to a variant for unknown strings.  A unit variant marked
+//! `cstr(default)` is parsed from its own name and from any unknown string.  A variant marked `cstr(other)` is
+//! parsed from any string not matching another variant, and may capture that string in a single `CString` or
+//! `Cow<'static, CStr>` field.  A `Cow` is always `Cow::Owned` when parsed, but lets code elsewhere build the
+//! variant from a borrowed name without allocating.  At most one of these may appear in an enum.  Every other name,
+//! even an empty one, is compared before falling back, so the `other` variant cannot shadow another variant, and
+//! giving it an alias is a compile error.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::{CStr, CString};
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(other)]
+//!   Other(CString),
+//! }
+//!
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"Durian\0").unwrap();
+//! assert_eq!(Constants::from_cstr(returned_from_c_api), Ok(Constants::Other(returned_from_c_api.to_owned())));
+//! ```
+//!
+//! A variant marked `cstr(skip)` still has a name for `as_cstr`, but is never parsed by `from_cstr` or looked up by
+//! `cstr_for_str`, so its name is treated like any unknown string.  It cannot also have an alias.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(skip)]
+//!   Internal,
+//! }
+//!
+//! assert_eq!(Constants::Internal.as_cstr().to_bytes(), b"Internal");
+//! assert!(Constants::from_cstr(CStr::from_bytes_with_nul(b"Internal\0").unwrap()).is_err());
+//! ```
+//!
+//! Two variants with the same name are a compile error, whether the names are written out or come from the idents.
+//! Variants may deliberately share a name, such as a deprecated variant kept alongside its replacement, with the
+//! `cstr(allow_duplicate_emit)` flag on the enum.  Both return the name from `as_cstr`, and exactly one variant of
+//! each group sharing a name must be marked `cstr(canonical)`, which is the one `from_cstr` returns.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! #[cstr(allow_duplicate_emit)]
+//! enum Color {
+//!   #[cstr(name="gray")]
+//!   #[deprecated]
+//!   Grey,
+//!   #[cstr(name="gray", canonical)]
+//!   Gray,
+//! }
+//!
+//! # #[allow(deprecated)]
+//! assert_eq!(Color::Grey.as_cstr(), Color::Gray.as_cstr());
+//! assert_eq!(Color::from_cstr(CStr::from_bytes_with_nul(b"gray\0").unwrap()), Ok(Color::Gray));
+//! ```
+//!
+//! Names meant to match C identifiers can be checked with `cstr(validate_charset="...")` on the enum, which is one of
+//! `"ascii_alnum"`, `"ascii_alnum_underscore"` or `"ascii_printable"` (which includes the space).  Any name or alias
+//! with a character outside the charset is a compile error, though names from `name_fn`, `cstr` or `rename_with` are
+//! only known later and aren't checked.
+//! ```compile_fail
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[cstr(validate_charset="ascii_alnum_underscore")]
+//! enum Constants {
+//!   #[cstr(name="max value")]
+//!   MaxValue,
+//! }
+//! ```
+//!
+//! Deriving `FromCStr` for an enum with no variants and no fallback produces a warning, since `from_cstr` can never
+//! succeed.  The `cstr(deny_unparseable)` flag on the enum turns the warning into an error.
+//!
+//! The `cstr(case_insensitive)` flag on the enum makes `from_cstr` ignore ASCII case, without changing the names.
+//! Names which only differ by case are rejected at compile time.  Only ASCII letters are folded, so non-ASCII UTF-8
+//! names such as `"Été"` must still match the case of any non-ASCII characters exactly.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! #[cstr(case_insensitive)]
+//! enum Constants {
+//!   Apple,
+//! }
+//!
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"APPLE\0").unwrap();
+//! assert_eq!(Constants::from_cstr(returned_from_c_api), Ok(Constants::Apple));
+//! assert_eq!(Constants::Apple.as_cstr().to_bytes(), b"Apple");
+//! ```
+//! Exact comparisons compile to a slice comparison, which is already vectorised by `memcmp`.  For enums with many
+//! names, `from_cstr` first switches on the length of the input, so only names of that length are compared.  For enums with long
+//! names, the `simd` feature makes case-insensitive comparisons work on eight bytes at a time, which is portable to
+//! every target and gives the same results.
+//!
+//! The `cstr(separator_insensitive)` flag on the enum makes `from_cstr` treat `-` and `_` as the same byte, while
+//! `as_cstr` still returns the name as written.  Names which only differ by separators are rejected at compile time.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr, AsCStr)]
+//! #[cstr(separator_insensitive)]
+//! enum Constants {
+//!   #[cstr(name="max-retries")]
+//!   MaxRetries,
+//! }
+//!
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"max_retries\0").unwrap();
+//! assert_eq!(Constants::from_cstr(returned_from_c_api), Ok(Constants::MaxRetries));
+//! assert_eq!(Constants::MaxRetries.as_cstr().to_bytes(), b"max-retries");
+//! ```
+//!
+//! For C libraries which pad their strings, the `cstr(trim)` flag on the enum makes `from_cstr` and the other parsing
+//! functions ignore whitespace at either end of the input.  Only ASCII whitespace is trimmed, as by
+//! [`u8::is_ascii_whitespace`], so other whitespace such as a non-breaking space still has to match.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! #[cstr(trim)]
+//! enum Constants {
+//!   Apple,
+//! }
+//!
+//! let padded = CStr::from_bytes_with_nul(b"  Apple \t\0").unwrap();
+//! assert_eq!(Constants::from_cstr(padded), Ok(Constants::Apple));
+//! ```
+//!
+//! The error type of the derived `FromCStr` implementation is normally a `&'static str` message, or a
+//! `ParseCStrError` with the `rich_error` feature.  That feature is not additive: it changes the error of every
+//! derived `FromCStr` in the build, including those of other crates, so code matching on or comparing with the
+//! `&'static str` error stops compiling when any crate enables it.  Libraries should leave it to the final binary,
+//! and use `cstr(error = "...")` or `cstr(unknown_variant_error)` (see below) for a richer error on a single enum.
+//!
+//! The `cstr(unit_error)` flag on the enum makes the error `()` instead, so the message string is not stored in the
+//! binary at all.  This is only worthwhile on targets where every byte of flash counts, since callers can no longer
+//! tell which enum failed to parse.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! #[cstr(unit_error)]
+//! enum Constants {
+//!   Apple,
+//! }
+//!
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"unknown\0").unwrap();
+//! assert_eq!(Constants::from_cstr(returned_from_c_api), Err(()));
+//! ```
+//! To use an error type of your own, give its path with `cstr(error = "path::to::Error")` on the enum.  The derived
+//! implementations build it with `From<&'static str>`, from the same message as the default error.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq)]
+//! struct MyError(&'static str);
+//!
+//! impl From<&'static str> for MyError {
+//!   fn from(msg: &'static str) -> Self {
+//!     MyError(msg)
+//!   }
+//! }
+//!
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! #[cstr(error = "MyError")]
+//! enum Constants {
+//!   Apple,
+//! }
+//!
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"unknown\0").unwrap();
+//! assert_eq!(Constants::from_cstr(returned_from_c_api), Err(MyError("unexpected string while parsing for Constants variant")));
+//! ```
+//! With the `alloc` feature, the `cstr(unknown_variant_error)` flag on the enum makes the error an [`UnknownVariant`],
+//! which keeps a copy of the input that failed to parse.  This is not supported by `FromCWStr`.
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! #[cstr(unknown_variant_error)]
+//! enum Constants {
+//!   Apple,
+//! }
+//!
+//! let err = Constants::from_cstr(CStr::from_bytes_with_nul(b"pear\0").unwrap()).unwrap_err();
+//! assert_eq!(err.got.as_bytes(), b"pear");
+//! assert_eq!(err.to_string(), "unexpected string \"pear\" while parsing for Constants variant");
+//! # }
+//! ```
+//!
+//! With the `cstr(prefix_fallback)` flag on the enum, `from_cstr` falls back to the variant whose name starts with the
+//! input when there is no exact match.  An exact match always wins, and an input which is a prefix of several names
+//! is still an error.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! #[cstr(prefix_fallback)]
+//! enum Constants {
+//!   Apple,
+//!   Apricot,
+//! }
+//!
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"Apr\0").unwrap();
+//! assert_eq!(Constants::from_cstr(returned_from_c_api), Ok(Constants::Apricot));
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"Ap\0").unwrap();
+//! assert!(Constants::from_cstr(returned_from_c_api).is_err());
+//! ```
+//!
+//! By default, `from_cstr` compares its input with each name in turn.  With `cstr(lookup="binary_search")` on the enum,
+//! the names are instead sorted at compile time and binary searched, which is faster for large enums.  This requires
+//! literal names and exact matching, so it cannot be combined with `name_fn`, `cstr`, `normalize`, `case_insensitive`
+//! or `separator_insensitive`.  A compile-time assertion checks that the generated table really is sorted.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! #[cstr(lookup="binary_search")]
+//! enum Constants {
+//!   Cat,
+//!   Bacon,
+//!   Apple,
+//! }
+//!
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"Bacon\0").unwrap();
+//! assert_eq!(Constants::from_cstr(returned_from_c_api), Ok(Constants::Bacon));
+//! ```
+//!
+//! For generic code using the standard conversion traits, deriving `FromCStr` also implements `TryFrom<&CStr>`,
+//! which calls `from_cstr` and has the same error type.
+//!
+//! Deriving `FromCStr` also generates an associated `CSTR_NAME_MAX_LEN` constant and a `from_byte_iter` function,
+//! which parses bytes from an iterator (up to the first nul byte) for input which is not contiguous in memory.
+//! The bytes are buffered on the stack, so no allocation is performed.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! enum Constants {
+//!   Apple,
+//!   Bacon,
+//! }
+//!
+//! assert_eq!(Constants::CSTR_NAME_MAX_LEN, 5);
+//! assert_eq!(Constants::from_byte_iter(b"Bacon\0".iter().copied()), Ok(Constants::Bacon));
+//! ```
+//! For a buffer which isn't nul-terminated, `from_cstr_bytes` parses the bytes directly.  The input must not include
+//! the nul terminator, since no name contains a nul byte, so `b"Bacon\0"` is an unknown name.
+//! ```
+//! # use cstr_enum::*;
+//! # #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! # enum Constants {
+//! #   Apple,
+//! #   Bacon,
+//! # }
+//! assert_eq!(Constants::from_cstr_bytes(b"Bacon"), Ok(Constants::Bacon));
+//! assert!(Constants::from_cstr_bytes(b"Bacon\0").is_err());
+//! ```
+//!
+//! For forgiving interactive input, the generated `from_cstr_fuzzy` function returns the variant with the name closest
+//! to the input within a given number of edits, counting single-byte insertions, deletions and substitutions.  This
+//! is not an exact parse, so unlike `from_cstr` it may accept a typo, and it gives `None` rather than pick between
+//! variants which are equally close.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! enum Command {
+//!   Start,
+//!   Stop,
+//!   Status,
+//! }
+//!
+//! let cstr = |b| CStr::from_bytes_with_nul(b).unwrap();
+//! assert_eq!(Command::from_cstr_fuzzy(cstr(b"Statsu\0"), 2), Some(Command::Status));
+//! assert_eq!(Command::from_cstr_fuzzy(cstr(b"Sta\0"), 2), None);
+//! ```
+//! For messages such as "did you mean ...?", deriving `AsCStr` gives `closest_name`, which returns the name itself
+//! rather than a variant.  The input may be at most one edit away for every three bytes, and at least one edit, so
+//! only plausible typos get a suggestion.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(AsCStr)]
+//! enum Command {
+//!   #[cstr(name="start")]
+//!   Start,
+//!   #[cstr(name="status")]
+//!   Status,
+//! }
+//!
+//! let cstr = |b| CStr::from_bytes_with_nul(b).unwrap();
+//! assert_eq!(Command::closest_name(cstr(b"statsu\0")), Some(cstr(b"status\0")));
+//! assert_eq!(Command::closest_name(cstr(b"quit\0")), None);
+//! ```
+//!
+//! The generated `from_cstr_field` function parses a fixed-size, nul-padded field out of a larger buffer, such as a
+//! packed record from a C API.  A field which is out of bounds gives an error rather than panicking.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! enum Constants {
+//!   Apple,
+//!   Bacon,
+//! }
+//!
+//! let record = b"\x01\x02Bacon\0\0\0\x03";
+//! assert_eq!(Constants::from_cstr_field(record, 2, 8), Ok(Constants::Bacon));
+//! assert!(Constants::from_cstr_field(record, 2, 16).is_err());
+//! ```
+//! Similarly, `from_pascal_bytes` parses a length-prefixed name, whose length is given by the first byte.
+//! ```
+//! # use cstr_enum::*;
+//! # #[derive(Debug, Eq, PartialEq, FromCStr)]
+//! # enum Constants {
+//! #   Apple,
+//! # }
+//! assert_eq!(Constants::from_pascal_bytes(b"\x05Apple"), Ok(Constants::Apple));
+//! assert!(Constants::from_pascal_bytes(b"\x09Apple").is_err());
+//! ```
+//!
+//! With the `alloc` feature, deriving `AsCStr` also implements `From<&T>` for [`CString`](std::ffi::CString) and
+//! [`String`], for collecting names into owned containers.  Every name must be valid UTF-8, which is checked at
+//! compile time, unless the enum has the `cstr(no_string)` flag.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(name="pork")]
+//!   Bacon,
+//! }
+//!
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! let names: Vec<String> = [Constants::Apple, Constants::Bacon].iter().map(String::from).collect();
+//! assert_eq!(names, ["Apple", "pork"]);
+//! # }
+//! ```
+//! With the `std` feature, it also generates `lookup_in`, which looks up the name of a variant in a
+//! `HashMap<&'static CStr, V>`, such as configuration keyed by the names a C library uses.
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use cstr_enum::*;
+//! # use std::collections::HashMap;
+//! # use std::ffi::CStr;
+//! #[derive(AsCStr)]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(name="pork")]
+//!   Bacon,
+//! }
+//!
+//! let mut prices = HashMap::new();
+//! prices.insert(CStr::from_bytes_with_nul(b"pork\0").unwrap(), 3);
+//! assert_eq!(Constants::Bacon.lookup_in(&prices), Some(&3));
+//! assert_eq!(Constants::Apple.lookup_in(&prices), None);
+//! # }
+//! ```
+//!
+//! On targets where flash is scarce, `cstr(total_bytes_max = N)` on the enum makes deriving `AsCStr` check at compile
+//! time that the names take up at most `N` bytes in total, including their nul terminators.
+//! ```compile_fail
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[cstr(total_bytes_max = 8)]
+//! enum Constants {
+//!   Apple, // 6 bytes
+//!   Bacon, // 6 bytes
+//! }
+//! ```
+//!
+//! Deriving `AsCStr` also generates an associated `CSTR_VARIANTS` constant, listing the name of every variant
+//! in declaration order.  Variants with fields are listed by name like any other, while aliases are not listed.  The
+//! order is part of the API: the name of the `n`th variant is always `CSTR_VARIANTS[n]`, apart from `transparent`
+//! variants, which are left out.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(name="pork", alias="bacon")]
+//!   Bacon { grams: u32 },
+//!   Cat(u8),
+//! }
+//!
+//! let names: Vec<_> = Constants::CSTR_VARIANTS.iter().map(|n| n.to_bytes()).collect();
+//! assert_eq!(names, [&b"Apple"[..], b"pork", b"Cat"]);
+//! ```
+//! Each of these names is also an associated constant of its own, named after the variant in `SCREAMING_SNAKE_CASE`
+//! with a `CSTR_` prefix, so a name can be used without constructing the variant.  Variants which would share a
+//! constant, such as `HttpServer` and `HTTPServer`, are a compile error.  A variant named `Variants` or `NameMaxLen`
+//! gets no constant, as it would clash with `CSTR_VARIANTS` or `CSTR_NAME_MAX_LEN`.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! enum Constants {
+//!   #[cstr(name="pork")]
+//!   SmokedBacon { grams: u32 },
+//! }
+//!
+//! assert_eq!(Constants::CSTR_SMOKED_BACON.to_bytes(), b"pork");
+//! ```
+//! For fixed-layout records, the `cstr(emit_lengths)` flag on the enum also generates a `CSTR_VARIANT_LENS` array
+//! with the length in bytes of each name in `CSTR_VARIANTS`, without the nul terminator.  A variant named
+//! `VariantLens` then gets no constant of its own.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[cstr(emit_lengths)]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(name="pork")]
+//!   Bacon,
+//! }
+//!
+//! const RECORD_LEN: usize = Constants::CSTR_VARIANT_LENS[1] + 1;
+//! assert_eq!(Constants::CSTR_VARIANT_LENS, [5, 4]);
+//! assert_eq!(RECORD_LEN, 5);
+//! ```
+//! The `match` in a derived `as_cstr` is usually inlined into its callers, which is fast but can add up for enums
+//! with hundreds of variants used in many places.  The `cstr(outline)` flag on the enum moves it into a separate
+//! function marked `#[inline(never)]`, so each call site is only a call.  This trades a little speed for smaller code,
+//! and is best left off unless the size of the binary matters more than every name lookup.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[cstr(outline)]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(name="pork")]
+//!   Bacon,
+//! }
+//!
+//! assert_eq!(Constants::Bacon.as_cstr().to_bytes(), b"pork");
+//! ```
+//! For frameworks with their own trait for naming things in C, such as `trait HasCName { fn c_name(&self) -> &CStr; }`,
+//! the `cstr(also_impl="path::to::Trait::method")` option on the enum also implements that trait, with the method
+//! returning the name from `as_cstr`.  The method must take `&self` and return `&CStr`, and any other methods of the
+//! trait must have default implementations.  The option may be given more than once.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! trait HasCName {
+//!   fn c_name(&self) -> &CStr;
+//! }
+//!
+//! #[derive(AsCStr)]
+//! #[cstr(also_impl="HasCName::c_name")]
+//! enum Constants {
+//!   #[cstr(name="pork")]
+//!   Bacon,
+//! }
+//!
+//! assert_eq!(Constants::Bacon.c_name().to_bytes(), b"pork");
+//! ```
+//! The [`assert_globally_unique`] macro uses these to check that several enums do not share any names.
+//! For enums with only unit variants, `all_variants_and_names` returns the variants and names as parallel slices,
+//! which is convenient for exhaustive tests and benchmarks, and `cstr_variants` iterates over `(variant, name)` pairs,
+//! such as for building lookup tables at startup.  Enums with fields get neither, as their variants can't be made
+//! without values for the fields.
+//! Enums with only unit variants also get `variants_with_prefix`, which yields every variant with a name starting
+//! with a prefix, for completing partially typed names.  With `cstr(lookup = "binary_search")` the names are sorted at compile time, so
+//! only the matching range is scanned and the variants come in order of their names.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(Debug, PartialEq, AsCStr)]
+//! enum Command {
+//!   Help,
+//!   History,
+//!   Quit,
+//! }
+//!
+//! let found: Vec<_> = Command::variants_with_prefix(CStr::from_bytes_with_nul(b"H\0").unwrap()).collect();
+//! assert_eq!(found, [Command::Help, Command::History]);
+//! ```
+//!
+//! The generated `cstr_for_str` function looks up the variant named by a Rust `&str`, returning its name as a
+//! `&'static CStr` for passing on to C.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! enum Constants {
+//!   #[cstr(name="pork")]
+//!   Bacon,
+//! }
+//!
+//! assert_eq!(Constants::cstr_for_str("pork"), Some(Constants::Bacon.as_cstr()));
+//! assert_eq!(Constants::cstr_for_str("Bacon"), None);
+//! ```
+//!
+//! Unless the enum has a `transparent` variant, there is also a `const fn is(&self, name: &[u8]) -> bool` comparing the
+//! name (without the nul terminator) with `name`, so static configuration can branch on names at compile time.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! enum Constants {
+//!   Apple,
+//!   Bacon,
+//! }
+//!
+//! const APPLE_IS_APPLE: bool = Constants::Apple.is(b"Apple");
+//! assert!(APPLE_IS_APPLE);
+//! assert!(!Constants::Bacon.is(b"Apple"));
+//! ```
+//! Such enums also get `const fn as_cstr_const(&self) -> &'static CStr`, returning the same name as `as_cstr`, for
+//! building tables of names at compile time.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(AsCStr)]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(name="pork")]
+//!   Bacon,
+//! }
+//!
+//! static TABLE: [&CStr; 2] = [Constants::Bacon.as_cstr_const(), Constants::Apple.as_cstr_const()];
+//! assert_eq!(TABLE[0].to_bytes(), b"pork");
+//! ```
+//!
+//! The `cstr(emit_index)` flag on the enum makes `AsCStr` also generate `index` and `from_index` functions, which
+//! convert between unit variants and their positions in declaration order.  Positions always run from `0` to the number
+//! of variants, so they are not the same as the discriminants if any are given explicitly.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(Debug, Eq, PartialEq, AsCStr)]
+//! #[cstr(emit_index)]
+//! enum Constants {
+//!   Apple,
+//!   Cat = 1337,
+//! }
+//!
+//! assert_eq!(Constants::Cat.index(), 1);
+//! assert_eq!(Constants::from_index(1), Some(Constants::Cat));
+//! assert_eq!(Constants::CSTR_VARIANTS[Constants::Cat.index()], Constants::Cat.as_cstr());
+//! ```
+//!
+//! Similarly, the `cstr(emit_discriminant_conversions)` flag implements `From<T> for i64` and generates a
+//! `try_from_i64` function, which convert between unit variants and their discriminants.  The enum is then also checked
+//! at compile time to give back every variant from its discriminant and from its name, which catches names from
+//! `name_fn` or `cstr` which turn out to be the same.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(Debug, Eq, PartialEq, AsCStr)]
+//! #[cstr(emit_discriminant_conversions)]
+//! enum Constants {
+//!   Apple,
+//!   Cat = 1337,
+//! }
+//!
+//! assert_eq!(i64::from(Constants::Cat), 1337);
+//! assert_eq!(Constants::try_from_i64(1337), Some(Constants::Cat));
+//! assert_eq!(Constants::try_from_i64(1), None);
+//! ```
+//!
+//! For a `#[repr(u8)]` enum of unit variants, such as the tag of a C tagged union, `AsCStr` also generates
+//! `name_for_tag`, which looks up a name by discriminant.  When the discriminants are integer literals with no gaps,
+//! this indexes a table, and otherwise compares against each discriminant in turn.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[repr(u8)]
+//! enum Tag {
+//!   Int = 1,
+//!   Float,
+//! }
+//!
+//! assert_eq!(Tag::name_for_tag(2).unwrap().to_bytes(), b"Float");
+//! assert_eq!(Tag::name_for_tag(0), None);
+//! ```
+//!
+//! For crates which expose a C API, `cstr(emit_c_module = "module")` on the enum makes `AsCStr` also generate a module
+//! of `#[no_mangle]` statics, which C code can declare `extern` and link against.  For an enum `FooBar`, these are:
+//! - `FOO_BAR_COUNT`, the number of variants, as a `size_t`.
+//! - `FOO_BAR_NAMES`, the name of each variant in declaration order, as a `const char*` array of [`CStrPtr`]s.
+//! - `FOO_BAR_DISCRIMINANTS`, the discriminant of each variant in the same order, as an `int64_t` array.  This is only
+//!   generated if every variant is a unit variant.
+//!
+//! Since the symbols are unmangled, they must be unique across everything linked into the final binary.  The module
+//! refers to the enum through `super`, so the enum must not be defined inside a function.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[cstr(emit_c_module = "ffi")]
+//! enum Constants {
+//!   Apple,
+//!   Cat = 1337,
+//! }
+//!
+//! # fn main() {
+//! assert_eq!(ffi::CONSTANTS_COUNT, 2);
+//! assert_eq!(ffi::CONSTANTS_NAMES[1].as_cstr(), Constants::Cat.as_cstr());
+//! assert_eq!(ffi::CONSTANTS_DISCRIMINANTS, [0, 1337]);
+//! # }
+//! ```
+//!
+//! With the `alloc` feature, the `cstr(emit_c_header)` flag on the enum makes `AsCStr` also generate a
+//! `to_c_header(enum_name: &str) -> String` function, for writing a C header from a build script.  The header
+//! contains a C enum with the same discriminants, with each variant prefixed by `enum_name`, and a
+//! `{enum_name}_names` array of the names in the same order.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[cstr(emit_c_header)]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(name="pork")]
+//!   Bacon,
+//!   Cat = 1337,
+//! }
+//!
+//! # #[cfg(feature = "alloc")]
+//! assert_eq!(Constants::to_c_header("constants"), "\
+//! enum constants {
+//!   constants_Apple = 0,
+//!   constants_Bacon = 1,
+//!   constants_Cat = 1337,
+//! };
+//! static const char* constants_names[] = {
+//!   \"Apple\",
+//!   \"pork\",
+//!   \"Cat\",
+//! };
+//! ");
+//! ```
+//!
+//! For build scripts which generate several C artifacts, the `cstr(emit_metadata)` flag gets everything at once.  As
+//! well as `CSTR_VARIANTS` and `to_c_header` (with `alloc`), it generates two constants in declaration order:
+//! `CSTR_MAPPING`, pairing the identifier of each variant with its name, and `CSTR_DISCRIMINANTS`, with the
+//! discriminant of each variant as an `i128`.  Every variant must be a unit variant, and variants named `Mapping` or
+//! `Discriminants` get no name constant of their own.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr)]
+//! #[cstr(emit_metadata)]
+//! enum Constants {
+//!   Apple,
+//!   #[cstr(name="pork")]
+//!   Bacon,
+//!   Cat = 1337,
+//! }
+//!
+//! assert_eq!(Constants::CSTR_MAPPING[1], ("Bacon", Constants::Bacon.as_cstr()));
+//! assert_eq!(Constants::CSTR_DISCRIMINANTS, [0, 1, 1337]);
+//! # #[cfg(feature = "alloc")]
+//! assert!(Constants::to_c_header("constants").contains("constants_Cat = 1337"));
+//! ```
+//!
+//! The `CStrDispatch` derive macro maps names to callbacks.  It generates a `{Enum}Handlers<R>` struct with one
+//! `fn() -> R` field per variant, named after the variant in snake case, and a `dispatch` function which calls the
+//! handler for a name.  For an enum with no variants, the struct instead has a single `returns: PhantomData<fn() -> R>`
+//! field.
+//! ```
+//! # use cstr_enum::*;
+//! # use std::ffi::CStr;
+//! #[derive(CStrDispatch)]
+//! enum Command {
+//!   Start,
+//!   ResetAll,
+//! }
+//!
+//! let handlers = CommandHandlers { start: || "starting", reset_all: || "resetting" };
+//! let returned_from_c_api = CStr::from_bytes_with_nul(b"ResetAll\0").unwrap();
+//! assert_eq!(Command::dispatch(returned_from_c_api, &handlers), Some("resetting"));
+//! ```
+//!
+//! The `CStrDebugEscaped` derive macro implements `Debug` by printing the name given by `AsCStr`, escaping bytes
+//! which are not printable ASCII in the same way as `CStr`'s `Debug` implementation.  This keeps control characters
+//! in names out of logs and terminals.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr, CStrDebugEscaped)]
+//! enum Constants {
+//!   #[cstr(name="tab\there")]
+//!   Tab,
+//! }
+//!
+//! assert_eq!(format!("{:?}", Constants::Tab), r"tab\there");
+//! ```
+//!
+//! The `CStrDisplay` derive macro likewise implements `Display` by printing the name given by `AsCStr`, for logging.
+//! Bytes which are not valid UTF-8 are replaced with `U+FFFD`, as by `String::from_utf8_lossy`, rather than panicking.
+//! ```
+//! # use cstr_enum::*;
+//! #[derive(AsCStr, CStrDisplay)]
+//! #[cstr(no_string)]
+//! enum Constants {
+//!   #[cstr(name="pork")]
+//!   Bacon,
+//!   #[cstr(name=b"\xFFraw")]
+//!   Raw,
+//! }
+//!
+//! assert_eq!(Constants::Bacon.to_string(), "pork");
+//! assert_eq!(Constants::Raw.to_string(), "\u{FFFD}raw");
+//! ```
+//!
+//! With the `assert_covers` feature, deriving `AsCStr` also generates a `#[cfg(test)]` associated function
+//! `assert_covers(expected: &[&CStr])`, which panics unless the enum's names are exactly `expected` (in any order).
+//! This is useful for catching drift between bindings and the C header they mirror.
+//!
+//! For Windows APIs, the [`AsCWStr`] and [`FromCWStr`] traits and derive macros convert to and from wide (UTF-16)
+//! C strings in the same way, with the names encoded at compile time.  Variants whose name is held in a field, such
+//! as `transparent` ones, have no such name, so are rejected.
+//!
+//! With the `bumpalo` feature, the `AsCStrIn` trait and derive macro give names which may be built at runtime,
+//! allocating them in a [`Bump`](https://docs.rs/bumpalo/latest/bumpalo/struct.Bump.html) arena, while still
+//! returning compile-time names as they are.
+//!
+//! With the `serde` feature, the `cstr(serde)` flag on the enum makes deriving `AsCStr` also implement `Serialize`,
+//! writing the name as a string.  A name which isn't UTF-8 is a serialization error, rather than being changed to
+//! fit.  Deriving `FromCStr` likewise implements `Deserialize`, parsing the name from a string, or from bytes in
+//! binary formats, so names which aren't UTF-8 can be read too.  Borrowed input is parsed in place, without building
+//! a `CString`.  Enums without the flag are free to implement the serde traits themselves, and using the flag
+//! without the feature is an error.
+//! ```
+//! # #[cfg(feature = "serde")] {
+//! # use cstr_enum::*;
+//! #[derive(Debug, Eq, PartialEq, AsCStr, FromCStr)]
+//! #[cstr(serde)]
+//! enum Constants {
+//!   #[cstr(name="bacon")]
+//!   Bacon,
+//! }
+//!
+//! assert_eq!(serde_json::to_string(&Constants::Bacon).unwrap(), "\"bacon\"");
+//! assert_eq!(serde_json::from_str::<Constants>("\"bacon\"").unwrap(), Constants::Bacon);
+//! # }
+//! ```
+//!
+//! Without the default `std` feature the crate is `no_std`, and the derive macros only generate paths into `core` or
+//! this crate, so they can be used in `no_std` crates too.  The `alloc` feature keeps the parts which need an
+//! allocator, such as the conversions into [`CString`](std::ffi::CString), without the rest of `std`.
+//!
+//! The names are C strings first, but there are a few conversions to and from Rust strings ([`str`] and [`String`])
+//! as well: `From<&Enum> for String` with the `alloc` feature, `cstr_for_str` for finding the name matching a `&str`,
+//! and the string-based serde impls with `cstr(serde)`.
+use core::ffi::CStr;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// Conversion to a C-style string.
+///
+/// If using the derive macro, this will be a cheap conversion.
+///
+/// It is also implemented for references, and with the `alloc` feature for [`Box<T>`](std::boxed::Box),
+/// [`Rc<T>`](std::rc::Rc) and [`Arc<T>`](std::sync::Arc), delegating to the inner value.
+/// ```
+/// # use cstr_enum::*;
+/// use std::{rc::Rc, sync::Arc};
+///
+/// #[derive(AsCStr)]
+/// enum Constants {
+///   Apple,
+/// }
+///
+/// fn name<T: AsCStr>(value: &T) -> &[u8] {
+///   value.as_cstr().to_bytes()
+/// }
+///
+/// assert_eq!(name(&&Constants::Apple), b"Apple");
+/// assert_eq!(name(&Box::new(Constants::Apple)), b"Apple");
+/// assert_eq!(name(&Rc::new(Constants::Apple)), b"Apple");
+/// assert_eq!(name(&Arc::new(Constants::Apple)), b"Apple");
+/// ```
+///
+/// It is also implemented for the uninhabited [`Infallible`](std::convert::Infallible), so generic code bounded by
+/// `AsCStr` can be used with it in branches which are never taken.
+pub trait AsCStr {
+  /// Represent self as a [`&CStr`](std::ffi::CStr).
+  fn as_cstr(&self) -> &CStr;
+
+  /// Check whether the name of self is exactly `s`.
+  #[inline]
+  fn matches(&self, s: &CStr) -> bool {
+    self.as_cstr() == s
+  }
+
+  /// Check whether the name of self is `s`, ignoring ASCII case.
+  ///
+  /// This is independent of how a derived `FromCStr` implementation compares names.
+  /// ```
+  /// # use cstr_enum::*;
+  /// # use std::ffi::CStr;
+  /// #[derive(AsCStr)]
+  /// enum Constants {
+  ///   Apple,
+  /// }
+  ///
+  /// assert!(Constants::Apple.matches_ignore_case(CStr::from_bytes_with_nul(b"aPPLE\0").unwrap()));
+  /// assert!(!Constants::Apple.matches_ignore_case(CStr::from_bytes_with_nul(b"Apples\0").unwrap()));
+  /// assert!(!Constants::Apple.matches(CStr::from_bytes_with_nul(b"aPPLE\0").unwrap()));
+  /// ```
+  #[inline]
+  fn matches_ignore_case(&self, s: &CStr) -> bool {
+    self.as_cstr().to_bytes().eq_ignore_ascii_case(s.to_bytes())
+  }
+
+  /// Copy the name of self into an owned [`CString`](std::ffi::CString), such as for passing to C functions which
+  /// take ownership of it.  Requires the `alloc` feature.
+  /// ```
+  /// # use cstr_enum::*;
+  /// #[derive(AsCStr)]
+  /// enum Constants {
+  ///   Apple,
+  /// }
+  ///
+  /// assert_eq!(Constants::Apple.to_cstring().as_bytes(), b"Apple");
+  /// ```
+  #[cfg(feature = "alloc")]
+  #[inline]
+  fn to_cstring(&self) -> alloc::ffi::CString {
+    self.as_cstr().into()
+  }
+
+  /// Represent the name of self as an [`OsStr`](std::ffi::OsStr), byte for byte.  Requires the `std` feature.
+  ///
+  /// This is only available on Unix, where an `OsStr` can hold any bytes.  Elsewhere, names which aren't UTF-8
+  /// have no `OsStr` to convert to, so use `to_str` on the name from `as_cstr` instead.
+  /// ```
+  /// # use cstr_enum::*;
+  /// #[derive(AsCStr)]
+  /// #[cstr(no_string)]
+  /// enum Constants {
+  ///   #[cstr(name=b"\xFFraw")]
+  ///   Raw,
+  /// }
+  ///
+  /// assert_eq!(std::os::unix::ffi::OsStrExt::as_bytes(Constants::Raw.as_os_str()), b"\xFFraw");
+  /// ```
+  #[cfg(all(feature = "std", unix))]
+  #[inline]
+  fn as_os_str(&self) -> &std::ffi::OsStr {
+    std::os::unix::ffi::OsStrExt::from_bytes(self.as_cstr().to_bytes())
+  }
+
+  /// Copy the name of self into an owned [`OsString`](std::ffi::OsString), byte for byte.  Requires the `std`
+  /// feature, and like `as_os_str` is only available on Unix.
+  /// ```
+  /// # use cstr_enum::*;
+  /// #[derive(AsCStr)]
+  /// enum Constants {
+  ///   Apple,
+  /// }
+  ///
+  /// assert_eq!(Constants::Apple.to_os_string(), "Apple");
+  /// ```
+  #[cfg(all(feature = "std", unix))]
+  #[inline]
+  fn to_os_string(&self) -> std::ffi::OsString {
+    self.as_os_str().to_owned()
+  }
+
+  /// Return a pointer to the name of self, as taken by many C functions.
+  ///
+  /// The pointer is only valid for as long as the borrow of self, which for derived implementations without
+  /// `transparent` variants is `'static`.
+  /// ```
+  /// # use cstr_enum::*;
+  /// # use std::ffi::CStr;
+  /// #[derive(AsCStr)]
+  /// enum Constants {
+  ///   Apple,
+  /// }
+  ///
+  /// let ptr = Constants::Apple.as_ptr();
+  /// assert_eq!(unsafe { CStr::from_ptr(ptr) }.to_bytes(), b"Apple");
+  /// ```
+  #[inline]
+  fn as_ptr(&self) -> *const core::ffi::c_char {
+    self.as_cstr().as_ptr()
+  }
+}
+
+impl AsCStr for core::convert::Infallible {
+  fn as_cstr(&self) -> &CStr {
+    match *self {}
+  }
+}
+
+impl<T: AsCStr + ?Sized> AsCStr for &T {
+  fn as_cstr(&self) -> &CStr {
+    (**self).as_cstr()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: AsCStr + ?Sized> AsCStr for alloc::boxed::Box<T> {
+  fn as_cstr(&self) -> &CStr {
+    (**self).as_cstr()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: AsCStr + ?Sized> AsCStr for alloc::rc::Rc<T> {
+  fn as_cstr(&self) -> &CStr {
+    (**self).as_cstr()
+  }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: AsCStr + ?Sized> AsCStr for alloc::sync::Arc<T> {
+  fn as_cstr(&self) -> &CStr {
+    (**self).as_cstr()
+  }
+}
+
+/// Conversion from a C-style string
+///
+/// This trait should be used the same way as [`std::str::FromStr`], although
+/// analogous `.parse()` method is not provided `&CStr`
+pub trait FromCStr {
+  /// The error type returned if parsing fails.
+  ///
+  /// If using the derive macro, this will be `&'static str`, or [`ParseCStrError`] if the `rich_error`
+  /// feature is enabled.  It is `()` if the enum has the `cstr(unit_error)` flag, or the type given by
+  /// `cstr(error = "...")`, or [`UnknownVariant`] with `cstr(unknown_variant_error)`.
+  type Err : Sized;
+  /// Parse the `&CStr` for an instance of `Self`.
+  ///
+  /// If using the derive macro, this will be a `match` statement over `&'static [u8]`.
+  fn from_cstr(s: &CStr) -> Result<Self, Self::Err> where Self: Sized;
+
+  /// Parse the `&CStr` into `out`, returning whether parsing succeeded.
+  ///
+  /// On failure, the error is discarded and `out` is left unchanged, so storage can be reused across calls
+  /// in a loop without handling a `Result` each time.
+  /// ```
+  /// # use cstr_enum::*;
+  /// # use std::ffi::CStr;
+  /// #[derive(Debug, Eq, PartialEq, FromCStr)]
+  /// enum Constants {
+  ///   Apple,
+  ///   Bacon,
+  /// }
+  ///
+  /// let mut out = None;
+  /// assert!(Constants::parse_into(CStr::from_bytes_with_nul(b"Apple\0").unwrap(), &mut out));
+  /// assert_eq!(out, Some(Constants::Apple));
+  /// assert!(!Constants::parse_into(CStr::from_bytes_with_nul(b"unknown\0").unwrap(), &mut out));
+  /// assert_eq!(out, Some(Constants::Apple));
+  /// ```
+  #[inline]
+  fn parse_into(s: &CStr, out: &mut Option<Self>) -> bool where Self: Sized {
+    match Self::from_cstr(s) {
+      Ok(v) => {
+        *out = Some(v);
+        true
+      }
+      Err(_) => false,
+    }
+  }
+
+  /// Parse the `&CStr` for an instance of `Self`, returning the input together with the error on failure.
+  ///
+  /// This lets callers report the offending string without copying it.
+  /// ```
+  /// # use cstr_enum::*;
+  /// # use std::ffi::CStr;
+  /// #[derive(Debug, Eq, PartialEq, FromCStr)]
+  /// #[cstr(unit_error)]
+  /// enum Constants {
+  ///   Apple,
+  /// }
+  ///
+  /// let apple = CStr::from_bytes_with_nul(b"Apple\0").unwrap();
+  /// assert_eq!(Constants::from_cstr_ref(apple), Ok(Constants::Apple));
+  /// let unknown = CStr::from_bytes_with_nul(b"unknown\0").unwrap();
+  /// assert_eq!(Constants::from_cstr_ref(unknown), Err(((), unknown)));
+  /// ```
+  #[inline]
+  fn from_cstr_ref(s: &CStr) -> Result<Self, (Self::Err, &CStr)> where Self: Sized {
+    Self::from_cstr(s).map_err(|e| (e, s))
+  }
+}
+
+/// Conversion both to and from a C-style string, for bounding generic code on a single trait.
+///
+/// This is implemented for every type which implements both [`AsCStr`] and [`FromCStr`], so deriving both gives it
+/// too.
+/// ```
+/// # use cstr_enum::*;
+/// #[derive(Debug, Eq, PartialEq, AsCStr, FromCStr)]
+/// enum Constants {
+///   Apple,
+///   #[cstr(name="pork")]
+///   Bacon,
+/// }
+///
+/// fn round_trip<T: CStrEnum>(value: &T) -> Option<T> {
+///   T::from_cstr(value.as_cstr()).ok()
+/// }
+///
+/// assert_eq!(round_trip(&Constants::Bacon), Some(Constants::Bacon));
+/// ```
+pub trait CStrEnum: AsCStr + FromCStr {}
+
+impl<T: AsCStr + FromCStr + ?Sized> CStrEnum for T {}
+
+/// Parse every string in `inputs`, such as an array of C strings returned together, keeping each result.
+/// ```
+/// # use cstr_enum::*;
+/// # use std::ffi::CStr;
+/// #[derive(Debug, Eq, PartialEq, FromCStr)]
+/// #[cstr(unit_error)]
+/// enum Constants {
+///   Apple,
+///   Bacon,
+/// }
+///
+/// let inputs = [CStr::from_bytes_with_nul(b"Bacon\0").unwrap(), CStr::from_bytes_with_nul(b"unknown\0").unwrap()];
+/// assert_eq!(parse_all::<Constants>(&inputs), [Ok(Constants::Bacon), Err(())]);
+/// assert_eq!(parse_all::<Constants>(&inputs[..1]), [Ok(Constants::Bacon)]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn parse_all<T: FromCStr>(inputs: &[&CStr]) -> alloc::vec::Vec<Result<T, T::Err>> {
+  inputs.iter().map(|s| T::from_cstr(s)).collect()
+}
+
+/// Parse every string in `inputs`, stopping at the first one which fails to parse.
+/// ```
+/// # use cstr_enum::*;
+/// # use std::ffi::CStr;
+/// #[derive(Debug, Eq, PartialEq, FromCStr)]
+/// #[cstr(unit_error)]
+/// enum Constants {
+///   Apple,
+///   Bacon,
+/// }
+///
+/// let apple = CStr::from_bytes_with_nul(b"Apple\0").unwrap();
+/// let unknown = CStr::from_bytes_with_nul(b"unknown\0").unwrap();
+/// assert_eq!(parse_all_ok::<Constants>(&[apple, apple]), Ok(vec![Constants::Apple, Constants::Apple]));
+/// assert_eq!(parse_all_ok::<Constants>(&[apple, unknown, apple]), Err(()));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn parse_all_ok<T: FromCStr>(inputs: &[&CStr]) -> Result<alloc::vec::Vec<T>, T::Err> {
+  inputs.iter().map(|s| T::from_cstr(s)).collect()
+}
+
+/// With the `std-impls` feature, [`Shutdown`](std::net::Shutdown) is named `"read"`, `"write"` and `"both"`.
+/// ```
+/// # use cstr_enum::*;
+/// # use std::ffi::CStr;
+/// use std::net::Shutdown;
+///
+/// assert_eq!(Shutdown::Both.as_cstr().to_bytes(), b"both");
+/// assert_eq!(Shutdown::from_cstr(CStr::from_bytes_with_nul(b"read\0").unwrap()).ok(), Some(Shutdown::Read));
+/// ```
+#[cfg(feature = "std-impls")]
+impl AsCStr for std::net::Shutdown {
+  fn as_cstr(&self) -> &CStr {
+    use std::net::Shutdown;
+    let name: &[u8] = match self {
+      Shutdown::Read => b"read\0",
+      Shutdown::Write => b"write\0",
+      Shutdown::Both => b"both\0",
+    };
+    // Every name above is nul-terminated, without interior nul bytes
+    unsafe { CStr::from_bytes_with_nul_unchecked(name) }
+  }
+}
+
+#[cfg(feature = "std-impls")]
+impl FromCStr for std::net::Shutdown {
+  type Err = __private::Error;
+
+  fn from_cstr(s: &CStr) -> Result<Self, Self::Err> {
+    use std::net::Shutdown;
+    match s.to_bytes() {
+      b"read" => Ok(Shutdown::Read),
+      b"write" => Ok(Shutdown::Write),
+      b"both" => Ok(Shutdown::Both),
+      bytes => Err(__private::unknown_variant("unexpected string while parsing for Shutdown variant", "Shutdown", bytes.is_empty())),
+    }
+  }
+}
+
+/// With the `std-impls` feature, [`Ordering`](std::cmp::Ordering) is named `"less"`, `"equal"` and `"greater"`.
+#[cfg(feature = "std-impls")]
+impl AsCStr for std::cmp::Ordering {
+  fn as_cstr(&self) -> &CStr {
+    use std::cmp::Ordering;
+    let name: &[u8] = match self {
+      Ordering::Less => b"less\0",
+      Ordering::Equal => b"equal\0",
+      Ordering::Greater => b"greater\0",
+    };
+    // Every name above is nul-terminated, without interior nul bytes
+    unsafe { CStr::from_bytes_with_nul_unchecked(name) }
+  }
+}
+
+#[cfg(feature = "std-impls")]
+impl FromCStr for std::cmp::Ordering {
+  type Err = __private::Error;
+
+  fn from_cstr(s: &CStr) -> Result<Self, Self::Err> {
+    use std::cmp::Ordering;
+    match s.to_bytes() {
+      b"less" => Ok(Ordering::Less),
+      b"equal" => Ok(Ordering::Equal),
+      b"greater" => Ok(Ordering::Greater),
+      bytes => Err(__private::unknown_variant("unexpected string while parsing for Ordering variant", "Ordering", bytes.is_empty())),
+    }
+  }
+}
+
+/// Conversion to a [`&CStr`](std::ffi::CStr) which may be built at runtime, allocating it in a [`bumpalo::Bump`]
+/// arena.  Requires the `bumpalo` feature.
+///
+/// If using the derive macro, variants with a name known at compile time return it without touching the arena, while
+/// a variant with `cstr(name_in="path::to::function")` has its name built by calling the function with the enum,
+/// returning anything which is `AsRef<[u8]>`, such as a `String`.  The name is then copied into the arena, panicking if it
+/// contains a nul byte.  The name of a `transparent` variant is copied into the arena too, since it borrows from the
+/// enum.  Either way, `as_cstr` still returns the usual name of the variant.
+/// ```
+/// # use cstr_enum::*;
+/// fn plugin_name(plugin: &Plugins) -> String {
+///   match plugin {
+///     Plugins::Loaded(id) => format!("plugin_{}", id),
+///     _ => unreachable!(),
+///   }
+/// }
+///
+/// #[derive(AsCStr, AsCStrIn)]
+/// enum Plugins {
+///   Builtin,
+///   #[cstr(name="plugin", name_in="plugin_name")]
+///   Loaded(u32),
+/// }
+///
+/// let arena = bumpalo::Bump::new();
+/// assert_eq!(Plugins::Builtin.as_cstr_in(&arena).to_bytes(), b"Builtin");
+/// assert_eq!(Plugins::Loaded(3).as_cstr_in(&arena).to_bytes(), b"plugin_3");
+/// assert_eq!(Plugins::Loaded(3).as_cstr().to_bytes(), b"plugin");
+/// ```
+#[cfg(feature = "bumpalo")]
+pub trait AsCStrIn<'a> {
+  /// Represent self as a [`&CStr`](std::ffi::CStr) living as long as `arena`, allocating it there if needed.
+  fn as_cstr_in(&self, arena: &'a bumpalo::Bump) -> &'a CStr;
+}
+
+/// Conversion to a nul-terminated wide (UTF-16) C string, as used by Windows APIs.
+///
+/// If using the derive macro, the names are encoded as UTF-16 at compile time, so this is a cheap conversion.
+/// Names given by `cstr(name_fn="...")` or `cstr(cstr="...")` are not supported.
+pub trait AsCWStr {
+  /// Represent self as a UTF-16 string, including the nul terminator.
+  fn as_cwstr(&self) -> &[u16];
+}
+
+/// Conversion from a wide (UTF-16) C string, as used by Windows APIs.
+pub trait FromCWStr {
+  /// The error type returned if parsing fails.
+  ///
+  /// If using the derive macro, this is the same as the error type of [`FromCStr`].
+  type Err : Sized;
+  /// Parse the UTF-16 string `s` for an instance of `Self`.
+  ///
+  /// Only the units before the first nul are compared, so `s` may or may not include the nul terminator.
+  /// ```
+  /// # use cstr_enum::*;
+  /// #[derive(Debug, Eq, PartialEq, AsCWStr, FromCWStr)]
+  /// enum Constants {
+  ///   Apple,
+  ///   Bacon,
+  /// }
+  ///
+  /// let wide = Constants::Bacon.as_cwstr();
+  /// assert_eq!(wide, [66, 97, 99, 111, 110, 0]);
+  /// assert_eq!(Constants::from_cwstr(wide), Ok(Constants::Bacon));
+  /// ```
+  fn from_cwstr(s: &[u16]) -> Result<Self, Self::Err> where Self: Sized;
+}
+
+/// The error returned by derived [`FromCStr`] implementations when the `rich_error` feature is enabled.
+///
+/// The feature applies to every derived implementation in the build, not only those of the crate enabling it, so
+/// it can break other crates which use the default `&'static str` error.  For a richer error on a single enum, use
+/// `cstr(error = "...")` or `cstr(unknown_variant_error)` on it instead.
+///
+/// With the `std` feature, this converts into a [`std::io::Error`] of kind [`InvalidData`](std::io::ErrorKind::InvalidData),
+/// so that `?` can be used in functions returning [`std::io::Result`].
+/// ```
+/// # use cstr_enum::*;
+/// # use std::ffi::CStr;
+/// #[derive(Debug, FromCStr)]
+/// enum Constants {
+///   Apple,
+/// }
+///
+/// fn parse(s: &CStr) -> std::io::Result<Constants> {
+///   Ok(Constants::from_cstr(s)?)
+/// }
+///
+/// let err = parse(CStr::from_bytes_with_nul(b"unknown\0").unwrap()).unwrap_err();
+/// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+/// ```
+#[cfg(feature = "rich_error")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseCStrError {
+  enum_name: &'static str,
+  kind: ErrorKind,
+}
+
+/// What went wrong when parsing, see the methods of [`ParseCStrError`]
+#[cfg(feature = "rich_error")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ErrorKind {
+  UnknownName,
+  EmptyInput,
+  OutOfRange,
+}
+
+#[cfg(feature = "rich_error")]
+impl ParseCStrError {
+  /// The name of the enum which was being parsed.
+  pub fn enum_name(&self) -> &'static str {
+    self.enum_name
+  }
+
+  /// Whether the input was empty.  Empty strings are often a sentinel, so they may need different handling from
+  /// other strings which don't name a variant.
+  /// ```
+  /// # use cstr_enum::*;
+  /// # use std::ffi::CStr;
+  /// #[derive(Debug, FromCStr)]
+  /// enum Constants {
+  ///   Apple,
+  /// }
+  ///
+  /// let err = Constants::from_cstr(CStr::from_bytes_with_nul(b"\0").unwrap()).unwrap_err();
+  /// assert!(err.is_empty_input());
+  /// let err = Constants::from_cstr(CStr::from_bytes_with_nul(b"unknown\0").unwrap()).unwrap_err();
+  /// assert!(!err.is_empty_input());
+  /// ```
+  pub fn is_empty_input(&self) -> bool {
+    self.kind == ErrorKind::EmptyInput
+  }
+
+  /// Whether the error came from a `from_cstr_field` range which was out of bounds, rather than an unknown name.
+  pub fn is_out_of_range(&self) -> bool {
+    self.kind == ErrorKind::OutOfRange
+  }
+}
+
+#[cfg(feature = "rich_error")]
+impl core::fmt::Display for ParseCStrError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self.kind {
+      ErrorKind::UnknownName => write!(f, "unexpected string while parsing for {} variant", self.enum_name),
+      ErrorKind::EmptyInput => write!(f, "empty string while parsing for {} variant", self.enum_name),
+      ErrorKind::OutOfRange => write!(f, "field out of range while parsing for {} variant", self.enum_name),
+    }
+  }
+}
+
+#[cfg(all(feature = "rich_error", feature = "std"))]
+impl std::error::Error for ParseCStrError {}
+
+// `core::error::Error` needs Rust 1.81, so it is only used where `std` isn't available
+#[cfg(all(feature = "rich_error", not(feature = "std")))]
+impl core::error::Error for ParseCStrError {}
+
+#[cfg(all(feature = "rich_error", feature = "std"))]
+impl From<ParseCStrError> for std::io::Error {
+  fn from(err: ParseCStrError) -> Self {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+  }
+}
+
+/// Which name of a variant was matched by `from_cstr_detailed`, as generated by deriving [`FromCStr`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MatchedName {
+  /// The variant's own name, as returned by `as_cstr`.
+  Canonical,
+  /// The variant's identifier, accepted because of `cstr(accept_ident)`.
+  Ident,
+  /// The alias at this index, counting the `cstr(alias)` attributes of the variant in the order they are written.
+  Alias(usize),
+  /// No name matched exactly, and the variant was chosen by `cstr(prefix_fallback)` or is the `other` variant.
+  Fallback,
+}
+
+/// The error of a derived `FromCStr` implementation with the `cstr(unknown_variant_error)` flag, holding the input
+/// which did not name a variant.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownVariant {
+  /// The input, up to its first nul byte.
+  pub got: alloc::ffi::CString,
+  /// The name of the enum which was being parsed.
+  pub enum_name: &'static str,
+}
+
+#[cfg(feature = "alloc")]
+impl core::fmt::Display for UnknownVariant {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "unexpected string {:?} while parsing for {} variant", self.got, self.enum_name)
+  }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownVariant {}
+
+#[cfg(all(feature = "alloc", not(feature = "std")))]
+impl core::error::Error for UnknownVariant {}
+
+#[cfg(feature = "std")]
+impl From<UnknownVariant> for std::io::Error {
+  fn from(err: UnknownVariant) -> Self {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+  }
+}
+
+/// A pointer to a static C string, with the same layout as a C `const char*`.
+///
+/// Unlike a raw pointer, this can be stored in a `static`, such as those generated by `cstr(emit_c_module = "...")`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CStrPtr(*const core::ffi::c_char);
+
+// The pointee is immutable and lives forever
+unsafe impl Sync for CStrPtr {}
+unsafe impl Send for CStrPtr {}
+
+impl CStrPtr {
+  /// Point to the start of `s`.
+  pub const fn new(s: &'static CStr) -> Self {
+    CStrPtr(s.as_ptr())
+  }
+
+  /// The raw pointer.
+  pub const fn as_ptr(self) -> *const core::ffi::c_char {
+    self.0
+  }
+
+  /// The string pointed to.
+  pub fn as_cstr(self) -> &'static CStr {
+    // Only constructed from `&'static CStr`
+    unsafe { CStr::from_ptr(self.0) }
+  }
+}
+
+/// Assert at compile time that no C string name is shared between the listed enums.
+///
+/// Each type must derive [`AsCStr`], and the check uses the generated `CSTR_VARIANTS` constants.  This is useful
+/// when many enums must have distinct names, for example to avoid clashes in a generated header.  Duplicated names,
+/// including duplicates within a single enum, cause a compile-time error.
+/// ```
+/// # use cstr_enum::*;
+/// #[derive(AsCStr)]
+/// enum Fruit {
+///   Apple,
+///   Banana,
+/// }
+///
+/// #[derive(AsCStr)]
+/// enum Meat {
+///   Bacon,
+///   #[cstr(name="Ham")]
+///   Pork,
+/// }
+///
+/// assert_globally_unique!(Fruit, Meat);
+/// ```
+/// ```compile_fail
+/// # use cstr_enum::*;
+/// # #[derive(AsCStr)]
+/// # enum Fruit {
+/// #   Apple,
+/// # }
+/// #[derive(AsCStr)]
+/// enum Company {
+///   Apple,
+/// }
+///
+/// assert_globally_unique!(Fruit, Company);
+/// ```
+#[macro_export]
+macro_rules! assert_globally_unique {
+  ($($ty:ty),+ $(,)?) => {
+    const _: () = $crate::__private::assert_globally_unique(&[$( <$ty>::CSTR_VARIANTS ),+]);
+  };
+}
+
+/// Call a closure with each variant of an enum, in declaration order.
+///
+/// The enum must derive [`AsCStr`] and have only unit variants, and the variants are produced by the generated
+/// `cstr_variants` function.  This is handy for registering every variant with a C API at startup.
+/// ```
+/// # use cstr_enum::*;
+/// #[derive(AsCStr)]
+/// enum Constants {
+///   Apple,
+///   #[cstr(name="pork")]
+///   Bacon,
+/// }
+///
+/// let mut names = Vec::new();
+/// for_each_variant!(Constants, |v| names.push(v.as_cstr().to_owned()));
+/// assert_eq!(names, Constants::CSTR_VARIANTS);
+/// ```
+#[macro_export]
+macro_rules! for_each_variant {
+  ($ty:ty, $f:expr $(,)?) => {
+    $crate::__private::for_each_variant(<$ty>::cstr_variants(), $f)
+  };
+}
+
+/// An interner for C-style strings which are only known at runtime.
+///
+/// Some names cannot be known by the derive macros, for example those registered by plugins at startup.
+/// Registered strings are leaked, so they remain valid for the rest of the program and can be handed
+/// out as `&'static CStr`.  Registering the same string twice returns the index of the first registration.
+/// Use [`impl_registry_as_cstr`] to implement [`AsCStr`] for a newtype over the returned indices.
+///
+/// ```
+/// use cstr_enum::*;
+/// use std::ffi::CString;
+///
+/// static PLUGINS: CStrRegistry = CStrRegistry::new();
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// struct PluginName(usize);
+///
+/// impl_registry_as_cstr!(PluginName, PLUGINS);
+///
+/// let foo = PluginName(PLUGINS.register(CString::new("foo").unwrap()));
+/// let bar = PluginName(PLUGINS.register(CString::new("bar").unwrap()));
+/// assert_eq!(foo.as_cstr().to_bytes_with_nul(), b"foo\0");
+/// assert_eq!(bar.as_cstr().to_bytes_with_nul(), b"bar\0");
+/// assert_eq!(PLUGINS.index_of(bar.as_cstr()), Some(bar.0));
+/// assert_eq!(PLUGINS.register(CString::new("foo").unwrap()), foo.0);
+/// assert_eq!(PLUGINS.len(), 2);
+/// ```
+#[cfg(feature = "std")]
+pub struct CStrRegistry {
+  names: std::sync::Mutex<Vec<&'static CStr>>,
+}
+
+#[cfg(feature = "std")]
+impl CStrRegistry {
+  /// Create an empty registry.  This can be used to initialise a `static`.
+  pub const fn new() -> Self {
+    CStrRegistry { names: std::sync::Mutex::new(Vec::new()) }
+  }
+
+  fn names(&self) -> std::sync::MutexGuard<'_, Vec<&'static CStr>> {
+    // The list is only ever pushed to, so it cannot be left in an inconsistent state.
+    self.names.lock().unwrap_or_else(|e| e.into_inner())
+  }
+
+  /// Intern `name`, returning its index in the registry.
+  pub fn register(&self, name: std::ffi::CString) -> usize {
+    let mut names = self.names();
+    if let Some(index) = names.iter().position(|&n| n == name.as_c_str()) {
+      return index;
+    }
+    names.push(Box::leak(name.into_boxed_c_str()));
+    names.len() - 1
+  }
+
+  /// Retrieve the string registered at `index`.
+  pub fn get(&self, index: usize) -> Option<&'static CStr> {
+    self.names().get(index).copied()
+  }
+
+  /// Find the index of a previously registered string.
+  pub fn index_of(&self, name: &CStr) -> Option<usize> {
+    self.names().iter().position(|&n| n == name)
+  }
+
+  /// The number of registered strings.
+  pub fn len(&self) -> usize {
+    self.names().len()
+  }
+
+  /// Returns `true` if no strings have been registered.
+  pub fn is_empty(&self) -> bool {
+    self.names().is_empty()
+  }
+}
+
+#[cfg(feature = "std")]
+impl Default for CStrRegistry {
+  fn default() -> Self {
+    CStrRegistry::new()
+  }
+}
+
+/// Implement [`AsCStr`] for a newtype over indices returned by [`CStrRegistry::register`].
+///
+/// The first argument is the newtype, which must wrap a `usize`, and the second is the registry.
+/// The generated `as_cstr` panics if the index was not returned by the registry.  See [`CStrRegistry`]
+/// for an example.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! impl_registry_as_cstr {
+  ($ty:ty, $registry:expr) => {
+    impl $crate::AsCStr for $ty {
+      fn as_cstr(&self) -> &::core::ffi::CStr {
+        $crate::CStrRegistry::get(&$registry, self.0).expect("index is not in the registry")
+      }
+    }
+  };
+}
+
+pub use cstr_enum_derive::*;
+
+/// Implementation details of the derive macros.
+#[doc(hidden)]
+pub mod __private {
+  #[cfg(feature = "alloc")]
+  pub use alloc::{ffi::CString, string::String, vec::Vec};
+  #[cfg(feature = "std")]
+  pub use std::{collections::HashMap, sync::OnceLock};
+
+  /// The error type used by derived `FromCStr` implementations.
+  #[cfg(not(feature = "rich_error"))]
+  pub type Error = &'static str;
+  /// The error type used by derived `FromCStr` implementations.
+  #[cfg(feature = "rich_error")]
+  pub type Error = super::ParseCStrError;
+
+  /// Construct the error returned when no variant matches.
+  #[cfg(not(feature = "rich_error"))]
+  pub fn unknown_variant(message: &'static str, _enum_name: &'static str, _empty_input: bool) -> Error {
+    message
+  }
+
+  /// Construct the error returned when no variant matches.
+  #[cfg(feature = "rich_error")]
+  pub fn unknown_variant(_message: &'static str, enum_name: &'static str, empty_input: bool) -> Error {
+    let kind = if empty_input { super::ErrorKind::EmptyInput } else { super::ErrorKind::UnknownName };
+    super::ParseCStrError { enum_name, kind }
+  }
+
+  /// Construct the error returned when a field is out of bounds of its buffer.
+  #[cfg(not(feature = "rich_error"))]
+  pub fn out_of_range(message: &'static str, _enum_name: &'static str) -> Error {
+    message
+  }
+
+  /// Construct the error returned when a field is out of bounds of its buffer.
+  #[cfg(feature = "rich_error")]
+  pub fn out_of_range(_message: &'static str, enum_name: &'static str) -> Error {
+    super::ParseCStrError { enum_name, kind: super::ErrorKind::OutOfRange }
+  }
+
+  /// Expands to its input only if the `assert_covers` feature is enabled.
+  #[cfg(feature = "assert_covers")]
+  #[macro_export]
+  #[doc(hidden)]
+  macro_rules! __cstr_enum_if_assert_covers {
+    ($($item:item)*) => { $($item)* };
+  }
+
+  /// Expands to its input only if the `assert_covers` feature is enabled.
+  #[cfg(not(feature = "assert_covers"))]
+  #[macro_export]
+  #[doc(hidden)]
+  macro_rules! __cstr_enum_if_assert_covers {
+    ($($item:item)*) => {};
+  }
+
+  pub use crate::__cstr_enum_if_assert_covers as if_assert_covers;
+
+  /// Expands to its input only if the `alloc` feature is enabled.
+  #[cfg(feature = "alloc")]
+  #[macro_export]
+  #[doc(hidden)]
+  macro_rules! __cstr_enum_if_alloc {
+    ($($item:item)*) => { $($item)* };
+  }
+
+  /// Expands to its input only if the `alloc` feature is enabled.
+  #[cfg(not(feature = "alloc"))]
+  #[macro_export]
+  #[doc(hidden)]
+  macro_rules! __cstr_enum_if_alloc {
+    ($($item:item)*) => {};
+  }
+
+  pub use crate::__cstr_enum_if_alloc as if_alloc;
+
+  /// Expands to its input only if the `std` feature is enabled.
+  #[cfg(feature = "std")]
+  #[macro_export]
+  #[doc(hidden)]
+  macro_rules! __cstr_enum_if_std {
+    ($($item:item)*) => { $($item)* };
+  }
+
+  /// Expands to its input only if the `std` feature is enabled.
+  #[cfg(not(feature = "std"))]
+  #[macro_export]
+  #[doc(hidden)]
+  macro_rules! __cstr_enum_if_std {
+    ($($item:item)*) => {};
+  }
+
+  pub use crate::__cstr_enum_if_std as if_std;
+
+  /// Expands to its input only if the `serde` feature is enabled.
+  #[cfg(feature = "serde")]
+  #[macro_export]
+  #[doc(hidden)]
+  macro_rules! __cstr_enum_if_serde {
+    ($($item:item)*) => { $($item)* };
+  }
+
+  /// Expands to its input only if the `serde` feature is enabled.  Only used for `cstr(serde)`, so without the
+  /// feature it is an error rather than nothing.
+  #[cfg(not(feature = "serde"))]
+  #[macro_export]
+  #[doc(hidden)]
+  macro_rules! __cstr_enum_if_serde {
+    ($($item:item)*) => {
+      ::core::compile_error!("`cstr(serde)` requires the `serde` feature of cstr-enum");
+    };
+  }
+
+  pub use crate::__cstr_enum_if_serde as if_serde;
+
+  #[cfg(feature = "serde")]
+  pub use serde;
+
+  /// Serialize `name` as a string, or fail if it is not UTF-8 rather than changing it.
+  #[cfg(feature = "serde")]
+  pub fn serialize_name<S: serde::Serializer>(name: &super::CStr, enum_name: &str, serializer: S) -> Result<S::Ok, S::Error> {
+    match name.to_str() {
+      Ok(name) => serializer.serialize_str(name),
+      Err(_) => Err(serde::ser::Error::custom(format_args!("name {:?} of a {} variant is not UTF-8", name, enum_name))),
+    }
+  }
+
+  /// Deserializes a name from a string or bytes, parsing it with `parse` without building a `CStr`.
+  #[cfg(feature = "serde")]
+  pub struct NameVisitor<T> {
+    /// The name of the enum, for error messages.
+    pub enum_name: &'static str,
+    /// Parses a name, which may include nul bytes.
+    pub parse: fn(&[u8]) -> Option<T>,
+  }
+
+  #[cfg(feature = "serde")]
+  impl<'de, T> serde::de::Visitor<'de> for NameVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+      write!(f, "a name of a {} variant", self.enum_name)
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<T, E> {
+      (self.parse)(v.as_bytes()).ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<T, E> {
+      (self.parse)(v).ok_or_else(|| E::invalid_value(serde::de::Unexpected::Bytes(v), &self))
+    }
+  }
+
+  /// Write a C enum named `enum_name` with the given `(ident, discriminant)` variants, followed by an array
+  /// `{enum_name}_names` of the corresponding `names`.
+  #[cfg(feature = "alloc")]
+  pub fn c_header(enum_name: &str, variants: &[(&str, i128)], names: &[&super::CStr]) -> String {
+    use core::fmt::Write;
+    let mut out = String::new();
+    writeln!(out, "enum {} {{", enum_name).unwrap();
+    for (ident, discriminant) in variants {
+      writeln!(out, "  {}_{} = {},", enum_name, ident, discriminant).unwrap();
+    }
+    writeln!(out, "}};").unwrap();
+    writeln!(out, "static const char* {}_names[] = {{", enum_name).unwrap();
+    for name in names {
+      out.push_str("  \"");
+      for &b in name.to_bytes() {
+        match b {
+          b'"' | b'\\' => { out.push('\\'); out.push(b as char); }
+          0x20..=0x7e => out.push(b as char),
+          // Always three digits, so a following digit is not part of the escape
+          _ => write!(out, "\\{:03o}", b).unwrap(),
+        }
+      }
+      out.push_str("\",\n");
+    }
+    writeln!(out, "}};").unwrap();
+    out
+  }
+
+  /// Panic unless `names` and `expected` contain the same strings, ignoring order.
+  #[cfg(feature = "assert_covers")]
+  pub fn assert_covers(enum_name: &str, names: &[&super::CStr], expected: &[&super::CStr]) {
+    let missing: Vec<_> = expected.iter().filter(|e| !names.contains(e)).collect();
+    let extra: Vec<_> = names.iter().filter(|n| !expected.contains(n)).collect();
+    if !missing.is_empty() || !extra.is_empty() {
+      panic!("names of {} do not match: missing {:?}, unexpected {:?}", enum_name, missing, extra);
+    }
+  }
+
+  /// Call `f` with each variant yielded by a generated `cstr_variants` function.
+  pub fn for_each_variant<T>(variants: impl Iterator<Item = (T, &'static super::CStr)>, mut f: impl FnMut(T)) {
+    for (v, _) in variants {
+      f(v);
+    }
+  }
+
+  /// Write `bytes`, escaping any which are not printable ASCII, as `CStr`'s `Debug` implementation does.
+  pub fn fmt_escaped(bytes: &[u8], f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    write!(f, "{}", bytes.escape_ascii())
+  }
+
+  /// The number of single-byte insertions, deletions or substitutions turning `a` into `b`, using `row` as
+  /// scratch space for at least `b.len() + 1` distances.
+  pub fn edit_distance(a: &[u8], b: &[u8], ignore_case: bool, row: &mut [usize]) -> usize {
+    let row = &mut row[..=b.len()];
+    for (j, d) in row.iter_mut().enumerate() {
+      *d = j;
+    }
+    for (i, &x) in a.iter().enumerate() {
+      // The distance between the previous prefixes of `a` and `b`
+      let mut diag = row[0];
+      row[0] = i + 1;
+      for (j, &y) in b.iter().enumerate() {
+        let same = x == y || (ignore_case && x.eq_ignore_ascii_case(&y));
+        let d = (diag + usize::from(!same)).min(row[j] + 1).min(row[j + 1] + 1);
+        diag = row[j + 1];
+        row[j + 1] = d;
+      }
+    }
+    row[b.len()]
+  }
+
+  /// The owner of the unique `(name, owner, ignore_case)` closest to `input`, within `max_distance` edits.  Names of
+  /// the same owner may tie, but a tie between owners gives `None`.
+  pub fn closest(input: &[u8], names: &[(&[u8], usize, bool)], max_distance: usize, row: &mut [usize]) -> Option<usize> {
+    let mut best: Option<(usize, usize)> = None;
+    let mut tied = false;
+    for &(name, owner, ignore_case) in names {
+      // The distance is at least the difference in length
+      if input.len().abs_diff(name.len()) > max_distance {
+        continue;
+      }
+      let d = edit_distance(input, name, ignore_case, row);
+      match best {
+        _ if d > max_distance => {}
+        Some((best_d, best_owner)) if d == best_d => tied |= best_owner != owner,
+        Some((best_d, _)) if d > best_d => {}
+        _ => {
+          best = Some((d, owner));
+          tied = false;
+        }
+      }
+    }
+    best.filter(|_| !tied).map(|(_, owner)| owner)
+  }
+
+  /// Write `bytes` as UTF-8, replacing invalid sequences with `U+FFFD` as `String::from_utf8_lossy` does.
+  pub fn fmt_lossy(bytes: &[u8], f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    for chunk in bytes.utf8_chunks() {
+      f.write_str(chunk.valid())?;
+      if !chunk.invalid().is_empty() {
+        f.write_str("\u{FFFD}")?;
+      }
+    }
+    Ok(())
+  }
+
+  /// Compare byte strings in a `const` context.
+  pub const fn bytes_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+      return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+      if a[i] != b[i] {
+        return false;
+      }
+      i += 1;
+    }
+    true
+  }
+
+  /// Check that byte strings are in strictly increasing lexicographic order, in a `const` context.
+  pub const fn is_sorted(names: &[&[u8]]) -> bool {
+    let mut i = 1;
+    while i < names.len() {
+      let (a, b) = (names[i - 1], names[i]);
+      let mut j = 0;
+      while j < a.len() && j < b.len() && a[j] == b[j] {
+        j += 1;
+      }
+      let less = if j < a.len() && j < b.len() { a[j] < b[j] } else { a.len() < b.len() };
+      if !less {
+        return false;
+      }
+      i += 1;
+    }
+    true
+  }
+
+  /// Compare byte strings ignoring ASCII case.
+  #[cfg(not(feature = "simd"))]
+  pub fn eq_ignore_ascii_case(a: &[u8], b: &[u8]) -> bool {
+    a.eq_ignore_ascii_case(b)
+  }
+
+  /// Compare byte strings ignoring ASCII case, a word at a time.
+  #[cfg(feature = "simd")]
+  pub fn eq_ignore_ascii_case(a: &[u8], b: &[u8]) -> bool {
+    use core::convert::TryInto;
+    /// Lowercase each ASCII byte of `x`, leaving other bytes unchanged
+    fn to_lower(x: u64) -> u64 {
+      const ONES: u64 = u64::from_ne_bytes([1; 8]);
+      let low = x & (0x7f * ONES);
+      // The high bit of each byte is set if the byte is above `Z`, or at least `A`, respectively
+      let above_z = low + (0x7f - b'Z' as u64) * ONES;
+      let from_a = low + (0x80 - b'A' as u64) * ONES;
+      let upper = !x & (above_z ^ from_a) & (0x80 * ONES);
+      x | (upper >> 2)
+    }
+    if a.len() != b.len() {
+      return false;
+    }
+    let (mut xs, mut ys) = (a.chunks_exact(8), b.chunks_exact(8));
+    for (x, y) in (&mut xs).zip(&mut ys) {
+      let x = u64::from_ne_bytes(x.try_into().unwrap());
+      let y = u64::from_ne_bytes(y.try_into().unwrap());
+      if to_lower(x) != to_lower(y) {
+        return false;
+      }
+    }
+    xs.remainder().eq_ignore_ascii_case(ys.remainder())
+  }
+
+  /// Check that no two byte strings are equal, in a `const` context.
+  pub const fn all_distinct(names: &[&[u8]]) -> bool {
+    let mut i = 0;
+    while i < names.len() {
+      let mut j = 0;
+      while j < i {
+        let (a, b) = (names[i], names[j]);
+        let mut k = 0;
+        while k < a.len() && k < b.len() && a[k] == b[k] {
+          k += 1;
+        }
+        if k == a.len() && k == b.len() {
+          return false;
+        }
+        j += 1;
+      }
+      i += 1;
+    }
+    true
+  }
+
+  /// Check whether `name` starts with `prefix`, optionally ignoring ASCII case and treating `-` and `_` as equal.
+  pub fn starts_with(name: &[u8], prefix: &[u8], ignore_case: bool, any_separator: bool) -> bool {
+    if name.len() < prefix.len() {
+      return false;
+    }
+    let name = &name[..prefix.len()];
+    match (ignore_case, any_separator) {
+      (false, false) => name == prefix,
+      (true, false) => eq_ignore_ascii_case(name, prefix),
+      (false, true) => eq_any_separator::<false>(name, prefix),
+      (true, true) => eq_any_separator::<true>(name, prefix),
+    }
+  }
+
+  /// Compare byte strings, treating `-` and `_` as equal and optionally ignoring ASCII case.
+  pub fn eq_any_separator<const IGNORE_CASE: bool>(a: &[u8], b: &[u8]) -> bool {
+    let sep = |b: u8| b == b'-' || b == b'_';
+    a.len() == b.len() && a.iter().zip(b).all(|(&x, &y)| {
+      x == y || (IGNORE_CASE && x.eq_ignore_ascii_case(&y)) || (sep(x) && sep(y))
+    })
+  }
+
+  /// The largest of `lens`, in a `const` context.
+  pub const fn max(lens: &[usize]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < lens.len() {
+      if lens[i] > max {
+        max = lens[i];
+      }
+      i += 1;
+    }
+    max
+  }
+
+  /// The units of `s` before the first nul.
+  pub fn wide_until_nul(s: &[u16]) -> &[u16] {
+    match s.iter().position(|&c| c == 0) {
+      Some(len) => &s[..len],
+      None => s,
+    }
+  }
+
+  /// Compare wide strings, optionally ignoring ASCII case and treating `-` and `_` as equal.
+  pub fn eq_wide(a: &[u16], b: &[u16], ignore_case: bool, any_separator: bool) -> bool {
+    let lower = |c: u16| if ignore_case && (b'A' as u16..=b'Z' as u16).contains(&c) { c + 32 } else { c };
+    let sep = |c: u16| any_separator && (c == b'-' as u16 || c == b'_' as u16);
+    a.len() == b.len() && a.iter().zip(b).all(|(&x, &y)| lower(x) == lower(y) || (sep(x) && sep(y)))
+  }
+
+  /// Validate the bytes returned by a `name_fn`, which is called in a `const` context.
+  pub const fn cstr_from_name_fn(bytes: &'static [u8]) -> &'static super::CStr {
+    match super::CStr::from_bytes_with_nul(bytes) {
+      Ok(s) => s,
+      Err(_) => panic!("`name_fn` must return nul-terminated bytes without interior nul bytes"),
+    }
+  }
+
+  #[cfg(feature = "bumpalo")]
+  pub use bumpalo::Bump;
+
+  /// Copy a name into `arena`, adding the nul terminator and panicking if it already contains a nul byte.
+  #[cfg(feature = "bumpalo")]
+  pub fn cstr_in<'a>(arena: &'a Bump, name: &[u8]) -> &'a super::CStr {
+    let buf = arena.alloc_slice_fill_copy(name.len() + 1, 0);
+    buf[..name.len()].copy_from_slice(name);
+    super::CStr::from_bytes_with_nul(buf).expect("`name_in` must return a name without nul bytes")
+  }
+
+  /// Convert the name returned by a `rename_with` function, panicking if it contains a nul byte.
+  #[cfg(feature = "alloc")]
+  pub fn cstring_from_rename_with(name: impl Into<Vec<u8>>) -> CString {
+    CString::new(name).expect("`rename_with` must return a name without nul bytes")
+  }
+
+  /// Construct an [`UnknownVariant`](super::UnknownVariant) from the input which did not match, up to its first nul.
+  #[cfg(feature = "alloc")]
+  pub fn unknown_variant_input(input: &[u8], enum_name: &'static str) -> super::UnknownVariant {
+    super::UnknownVariant { got: cstring_until_nul(input), enum_name }
+  }
+
+  /// Copy `input` up to its first nul byte into a `CString`.
+  #[cfg(feature = "alloc")]
+  pub fn cstring_until_nul(input: &[u8]) -> CString {
+    let len = input.iter().position(|&b| b == 0).unwrap_or(input.len());
+    // the bytes stop before the first nul
+    unsafe { CString::from_vec_unchecked(Vec::from(&input[..len])) }
+  }
+
+  /// `bytes` without ASCII whitespace at either end, for `cstr(trim)`.  The same as `<[u8]>::trim_ascii`, which
+  /// needs Rust 1.80.
+  pub fn trim_ascii(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(bytes.len());
+    let end = bytes.iter().rposition(|b| !b.is_ascii_whitespace()).map_or(start, |i| i + 1);
+    &bytes[start..end]
+  }
+
+  /// The bytes of `bytes` from `start` to `end`, in a `const` context.
+  pub const fn subslice(bytes: &[u8], start: usize, end: usize) -> &[u8] {
+    bytes.split_at(end).0.split_at(start).1
+  }
+
+  /// Convert a name to a `&str` in a `const` context, panicking if it is not valid UTF-8.
+  pub const fn cstr_to_str(name: &'static super::CStr) -> &'static str {
+    match core::str::from_utf8(name.to_bytes()) {
+      Ok(s) => s,
+      Err(_) => panic!("name must be valid UTF-8 to convert into a `String`"),
+    }
+  }
+
+  /// Panic (at compile time, if used in a `const`) if a name appears in more than one place across `names`.
+  pub const fn assert_globally_unique(names: &[&[&super::CStr]]) {
+    let mut i = 0;
+    while i < names.len() {
+      let mut j = 0;
+      while j < names[i].len() {
+        let name = names[i][j].to_bytes();
+        // Only compare with the names after this one
+        let mut k = i;
+        let mut l = j + 1;
+        while k < names.len() {
+          while l < names[k].len() {
+            if bytes_eq(name, names[k][l].to_bytes()) {
+              panic!("C string name is not globally unique");
+            }
+            l += 1;
+          }
+          k += 1;
+          l = 0;
+        }
+        j += 1;
+      }
+      i += 1;
+    }
+  }
+}
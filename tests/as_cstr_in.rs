@@ -0,0 +1,47 @@
+// Only built with `--features bumpalo`.
+#![cfg(feature = "bumpalo")]
+use bumpalo::Bump;
+use cstr_enum::*;
+use std::ffi::{CStr, CString};
+
+fn plugin_name(plugin: &Plugins) -> Vec<u8> {
+  match plugin {
+    Plugins::Loaded(id) => format!("plugin_{}", id).into_bytes(),
+    _ => unreachable!(),
+  }
+}
+
+#[derive(AsCStr, AsCStrIn)]
+enum Plugins {
+  Builtin,
+  #[cstr(name="core")]
+  Core,
+  #[cstr(name="plugin", name_in="plugin_name")]
+  Loaded(u32),
+  #[cstr(transparent)]
+  Named(CString),
+}
+
+#[test]
+fn static_names_skip_arena() {
+  let arena = Bump::new();
+  assert_eq!(Plugins::Builtin.as_cstr_in(&arena).to_bytes(), b"Builtin");
+  assert_eq!(Plugins::Core.as_cstr_in(&arena).to_bytes(), b"core");
+  assert_eq!(arena.allocated_bytes_including_metadata(), 0);
+  assert!(std::ptr::eq(Plugins::Core.as_cstr_in(&arena), Plugins::Core.as_cstr()));
+}
+
+#[test]
+fn dynamic_names_allocate() {
+  let arena = Bump::new();
+  let name = Plugins::Loaded(7).as_cstr_in(&arena);
+  assert_eq!(name.to_bytes_with_nul(), b"plugin_7\0");
+  assert!(arena.allocated_bytes() > 0);
+  assert_eq!(Plugins::Loaded(7).as_cstr().to_bytes(), b"plugin");
+
+  // The copy outlives the enum it was taken from
+  let named = Plugins::Named(CString::new("held").unwrap());
+  let copied: &CStr = named.as_cstr_in(&arena);
+  drop(named);
+  assert_eq!(copied.to_bytes(), b"held");
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "assert_covers")]
+use cstr_enum::*;
+use std::ffi::CStr;
+
+#[derive(AsCStr)]
+#[allow(dead_code)]
+enum Constants {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon,
+}
+
+fn cstr(bytes: &[u8]) -> &CStr {
+  CStr::from_bytes_with_nul(bytes).unwrap()
+}
+
+#[test]
+fn matching_names() {
+  Constants::assert_covers(&[cstr(b"pork\0"), cstr(b"Apple\0")]);
+}
+
+#[test]
+#[should_panic(expected = "names of Constants do not match")]
+fn missing_name() {
+  Constants::assert_covers(&[cstr(b"Apple\0"), cstr(b"pork\0"), cstr(b"Cat\0")]);
+}
+
+#[test]
+#[should_panic(expected = "names of Constants do not match")]
+fn unexpected_name() {
+  Constants::assert_covers(&[cstr(b"Apple\0")]);
+}
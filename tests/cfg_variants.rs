@@ -0,0 +1,36 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+// Also compiled outside of tests by tests/compilation/cfg_variants.rs
+#[derive(Debug, PartialEq, AsCStr, FromCStr, CStrDispatch)]
+#[cstr(emit_index)]
+enum Constants {
+  Apple,
+  #[cfg(test)]
+  #[cstr(name="mock")]
+  Mock,
+  #[cfg(not(test))]
+  Real,
+  Bacon,
+}
+
+fn cstr(bytes: &[u8]) -> &CStr {
+  CStr::from_bytes_with_nul(bytes).unwrap()
+}
+
+#[test]
+fn test_only_variant_is_named() {
+  assert_eq!(Constants::CSTR_VARIANTS, [cstr(b"Apple\0"), cstr(b"mock\0"), cstr(b"Bacon\0")]);
+  assert_eq!(Constants::Mock.as_cstr(), cstr(b"mock\0"));
+  assert_eq!(Constants::Bacon.index(), 2);
+}
+
+#[test]
+fn test_only_variant_is_parsed() {
+  assert_eq!(Constants::from_cstr(cstr(b"mock\0")), Ok(Constants::Mock));
+  assert!(Constants::from_cstr(cstr(b"Real\0")).is_err());
+  assert_eq!(Constants::CSTR_NAME_MAX_LEN, 5);
+
+  let handlers = ConstantsHandlers { apple: || 0, mock: || 1, bacon: || 2 };
+  assert_eq!(Constants::dispatch(cstr(b"mock\0"), &handlers), Some(1));
+}
@@ -0,0 +1,41 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr, CStrDispatch)]
+enum Enum0 {
+  #[cstr(name="pork", accept_ident)]
+  Bacon,
+  #[cstr(name="fruit")]
+  Apple,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(accept_ident, prefix_fallback)]
+enum Enum1 {
+  #[cstr(name="pork")]
+  Bacon,
+  Apple,
+}
+
+fn main() {
+  assert_eq!(Enum0::from_cstr(cstr(b"pork\0")), Ok(Enum0::Bacon));
+  assert_eq!(Enum0::from_cstr(cstr(b"Bacon\0")), Ok(Enum0::Bacon));
+  assert_eq!(Enum0::from_cstr(cstr(b"fruit\0")), Ok(Enum0::Apple));
+  assert!(Enum0::from_cstr(cstr(b"Apple\0")).is_err());
+  assert_eq!(Enum0::Bacon.as_cstr(), cstr(b"pork\0"));
+  assert_eq!(Enum0::CSTR_VARIANTS, [cstr(b"pork\0"), cstr(b"fruit\0")]);
+  assert_eq!(Enum0::from_byte_iter(b"Bacon".iter().copied()), Ok(Enum0::Bacon));
+  let handlers = Enum0Handlers { bacon: || 1, apple: || 2 };
+  assert_eq!(Enum0::dispatch(cstr(b"Bacon\0"), &handlers), Some(1));
+
+  assert_eq!(Enum1::from_cstr(cstr(b"Bacon\0")), Ok(Enum1::Bacon));
+  assert_eq!(Enum1::from_cstr(cstr(b"pork\0")), Ok(Enum1::Bacon));
+  assert_eq!(Enum1::from_cstr(cstr(b"Apple\0")), Ok(Enum1::Apple));
+  assert!(Enum1::from_cstr(cstr(b"\0")).is_err());
+  // names of the same variant don't make a prefix ambiguous
+  assert_eq!(Enum1::from_cstr(cstr(b"Ba\0")), Ok(Enum1::Bacon));
+  assert_eq!(Enum1::from_cstr(cstr(b"po\0")), Ok(Enum1::Bacon));
+}
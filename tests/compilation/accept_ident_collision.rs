@@ -0,0 +1,11 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+enum Enum0 {
+  #[cstr(name="pork", accept_ident)]
+  Bacon,
+  #[cstr(name="Bacon")]
+  Ham,
+}
+
+fn main() {}
@@ -0,0 +1,32 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr, FromCWStr)]
+enum Method {
+  #[cstr(name="GET", alias(value="get", case_insensitive), alias(value="fetch"))]
+  Get,
+  #[cstr(name="POST")]
+  Post,
+}
+
+fn main() {
+  // the canonical name matches exactly
+  assert_eq!(Method::from_cstr(cstr(b"GET\0")), Ok(Method::Get));
+  assert_eq!(Method::from_cstr(cstr(b"POST\0")), Ok(Method::Post));
+  assert!(Method::from_cstr(cstr(b"post\0")).is_err());
+  // the alias matches ignoring case
+  assert_eq!(Method::from_cstr(cstr(b"get\0")), Ok(Method::Get));
+  assert_eq!(Method::from_cstr(cstr(b"gEt\0")), Ok(Method::Get));
+  // other aliases are still exact
+  assert_eq!(Method::from_cstr(cstr(b"fetch\0")), Ok(Method::Get));
+  assert!(Method::from_cstr(cstr(b"FETCH\0")).is_err());
+  assert_eq!(Method::from_byte_iter(b"Get".iter().copied()), Ok(Method::Get));
+  assert_eq!(Method::from_cwstr(&[b'g' as u16, b'E' as u16, b'T' as u16, 0]), Ok(Method::Get));
+  assert!(Method::from_cwstr(&[b'F' as u16, b'E' as u16, b'T' as u16, b'C' as u16, b'H' as u16, 0]).is_err());
+  // aliases are never produced
+  assert_eq!(Method::Get.as_cstr(), cstr(b"GET\0"));
+  assert_eq!(Method::CSTR_VARIANTS, [cstr(b"GET\0"), cstr(b"POST\0")]);
+}
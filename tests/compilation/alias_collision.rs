@@ -0,0 +1,37 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+enum Enum0 {
+  #[cstr(alias="Gray")]
+  Grey,
+  Gray,
+}
+
+#[derive(FromCStr)]
+enum Enum1 {
+  Grey,
+  #[cstr(alias="colour", alias="Grey")]
+  Gray,
+}
+
+#[derive(FromCStr)]
+enum Enum2 {
+  #[cstr(alias="colour")]
+  Color,
+  #[cstr(alias="colour")]
+  Colour,
+}
+
+#[derive(FromCStr)]
+enum Enum3 {
+  #[cstr(alias="col\0r")]
+  Color,
+}
+
+#[derive(FromCStr)]
+enum Enum4 {
+  #[cstr(alias=1)]
+  Color,
+}
+
+fn main() {}
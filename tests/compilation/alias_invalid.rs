@@ -0,0 +1,29 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+enum Enum0 {
+  #[cstr(alias(value="POST", case_insensitive))]
+  Get,
+  #[cstr(name="post")]
+  Post,
+}
+
+#[derive(FromCStr)]
+enum Enum1 {
+  #[cstr(alias(case_insensitive))]
+  Get,
+}
+
+#[derive(FromCStr)]
+enum Enum2 {
+  #[cstr(alias(value="get", exact))]
+  Get,
+}
+
+#[derive(FromCStr)]
+enum Enum3 {
+  #[cstr(alias(value="g\0t"))]
+  Get,
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr, FromCWStr, CStrDispatch)]
+enum Setting {
+  #[cstr(name="color", alias="colour", alias="COLOR")]
+  Color,
+  #[cstr(alias="grey")]
+  Gray,
+}
+
+fn main() {
+  assert_eq!(Setting::from_cstr(cstr(b"color\0")), Ok(Setting::Color));
+  assert_eq!(Setting::from_cstr(cstr(b"colour\0")), Ok(Setting::Color));
+  assert_eq!(Setting::from_cstr(cstr(b"COLOR\0")), Ok(Setting::Color));
+  assert!(Setting::from_cstr(cstr(b"Colour\0")).is_err());
+  assert_eq!(Setting::from_cstr(cstr(b"Gray\0")), Ok(Setting::Gray));
+  assert_eq!(Setting::from_cstr(cstr(b"grey\0")), Ok(Setting::Gray));
+  assert_eq!(Setting::from_byte_iter(b"colour".iter().copied()), Ok(Setting::Color));
+  assert_eq!(Setting::from_cwstr(&[b'g' as u16, b'r' as u16, b'e' as u16, b'y' as u16, 0]), Ok(Setting::Gray));
+  let handlers = SettingHandlers { color: || 1, gray: || 2 };
+  assert_eq!(Setting::dispatch(cstr(b"colour\0"), &handlers), Some(1));
+
+  // only the canonical names are produced
+  assert_eq!(Setting::Color.as_cstr(), cstr(b"color\0"));
+  assert_eq!(Setting::Gray.as_cstr(), cstr(b"Gray\0"));
+  assert_eq!(Setting::CSTR_VARIANTS, [cstr(b"color\0"), cstr(b"Gray\0")]);
+}
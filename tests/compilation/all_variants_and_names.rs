@@ -0,0 +1,42 @@
+use cstr_enum::*;
+
+#[derive(Debug, PartialEq, AsCStr, FromCStr)]
+enum Enum {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon,
+  Cat = 1337,
+}
+
+// Not `Copy`, and dropping it has side effects
+#[derive(Debug, PartialEq, AsCStr)]
+enum Dropped {
+  A,
+  B,
+}
+
+impl Drop for Dropped {
+  fn drop(&mut self) {}
+}
+
+#[derive(AsCStr)]
+enum Empty {}
+
+const SLICES: (&[Enum], &[&std::ffi::CStr]) = Enum::all_variants_and_names();
+
+fn main() {
+  let (variants, names) = SLICES;
+  assert_eq!(variants.len(), names.len());
+  assert_eq!(variants, [Enum::Apple, Enum::Bacon, Enum::Cat]);
+  for (v, &name) in variants.iter().zip(names) {
+    assert_eq!(v.as_cstr(), name);
+    assert_eq!(Enum::from_cstr(name).as_ref(), Ok(v));
+  }
+
+  let (variants, names) = Dropped::all_variants_and_names();
+  assert_eq!(variants, [Dropped::A, Dropped::B]);
+  assert_eq!(names.len(), 2);
+
+  let (variants, names) = Empty::all_variants_and_names();
+  assert!(variants.is_empty() && names.is_empty());
+}
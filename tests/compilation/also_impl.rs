@@ -0,0 +1,41 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+mod ffi {
+  pub trait HasCName {
+    fn c_name(&self) -> &std::ffi::CStr;
+  }
+
+  pub trait Labelled {
+    fn label(&self) -> &std::ffi::CStr;
+
+    fn label_len(&self) -> usize {
+      self.label().to_bytes().len()
+    }
+  }
+}
+
+use ffi::Labelled;
+
+#[derive(AsCStr)]
+#[cstr(also_impl = "ffi::HasCName::c_name", also_impl = "crate::ffi::Labelled::label")]
+enum Constants<'a> {
+  Apple,
+  #[cstr(name = "pork")]
+  Bacon,
+  #[cstr(transparent)]
+  Held(&'a CStr),
+}
+
+fn c_name_of<T: ffi::HasCName>(v: &T) -> &CStr {
+  v.c_name()
+}
+
+fn main() {
+  assert_eq!(c_name_of(&Constants::Apple).to_bytes(), b"Apple");
+  assert_eq!(c_name_of(&Constants::Bacon), Constants::Bacon.as_cstr());
+  let held = CStr::from_bytes_with_nul(b"held\0").unwrap();
+  assert_eq!(c_name_of(&Constants::Held(held)), held);
+  assert_eq!(Constants::Bacon.label().to_bytes(), b"pork");
+  assert_eq!(Constants::Bacon.label_len(), 4);
+}
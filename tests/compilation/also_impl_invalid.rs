@@ -0,0 +1,19 @@
+use cstr_enum::*;
+
+trait HasCName {
+  fn c_name(&self) -> &std::ffi::CStr;
+}
+
+#[derive(AsCStr)]
+#[cstr(also_impl = "HasCName")]
+enum NoMethod {
+  Apple,
+}
+
+#[derive(AsCStr)]
+#[cstr(also_impl = "HasCName::c_name::<u8>")]
+enum Generic {
+  Apple,
+}
+
+fn main() {}
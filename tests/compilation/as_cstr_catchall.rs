@@ -0,0 +1,23 @@
+use cstr_enum::*;
+
+#[derive(Debug, AsCStr)]
+#[non_exhaustive]
+#[cstr(as_cstr_catchall = "unknown")]
+enum Enum {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon,
+}
+
+#[derive(AsCStr)]
+#[non_exhaustive]
+#[cstr(as_cstr_catchall = "unknown")]
+enum Empty {}
+
+fn main() {
+  assert_eq!(Enum::Apple.as_cstr().to_bytes(), b"Apple");
+  assert_eq!(Enum::Bacon.as_cstr().to_bytes(), b"pork");
+  // The catch-all is not a name of any variant
+  assert_eq!(Enum::CSTR_VARIANTS.len(), 2);
+  let _ = |e: &Empty| e.as_cstr().to_owned();
+}
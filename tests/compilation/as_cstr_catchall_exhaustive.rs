@@ -0,0 +1,16 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[cstr(as_cstr_catchall = "unknown")]
+enum Enum {
+  Apple,
+}
+
+#[derive(AsCStr)]
+#[non_exhaustive]
+#[cstr(as_cstr_catchall = "unk\0nown")]
+enum Nul {
+  Apple,
+}
+
+fn main() {}
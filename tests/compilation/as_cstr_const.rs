@@ -0,0 +1,35 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+const fn seed() -> &'static [u8] {
+  b"seed\0"
+}
+
+#[derive(AsCStr)]
+enum Constants {
+  Apple,
+  #[cstr(name = "pork")]
+  Bacon { grams: u32 },
+  #[cstr(name_fn = "seed")]
+  Seed(u8),
+}
+
+#[derive(AsCStr)]
+enum Generic<T> {
+  Held(T),
+}
+
+static TABLE: &CStr = Constants::Apple.as_cstr_const();
+static NAMES: [&CStr; 3] = [
+  Constants::Apple.as_cstr_const(),
+  Constants::Bacon { grams: 1 }.as_cstr_const(),
+  Constants::Seed(0).as_cstr_const(),
+];
+const HELD: &CStr = Generic::Held(1u8).as_cstr_const();
+
+fn main() {
+  assert_eq!(TABLE, Constants::Apple.as_cstr());
+  assert_eq!(NAMES, Constants::CSTR_VARIANTS);
+  assert_eq!(Constants::Bacon { grams: 2 }.as_cstr_const(), Constants::Bacon { grams: 2 }.as_cstr());
+  assert_eq!(HELD.to_bytes(), b"Held");
+}
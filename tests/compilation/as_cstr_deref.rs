@@ -0,0 +1,33 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+#[derive(AsCStr)]
+enum Constants {
+  Apple,
+}
+
+#[derive(AsCStr)]
+enum Held<'a> {
+  Red,
+  #[cstr(transparent)]
+  Other(&'a CStr),
+}
+
+fn name<T: AsCStr + ?Sized>(value: &T) -> &[u8] {
+  value.as_cstr().to_bytes()
+}
+
+fn main() {
+  assert_eq!((&Constants::Apple).as_cstr().to_bytes(), b"Apple");
+  assert_eq!(Box::new(Constants::Apple).as_cstr().to_bytes(), b"Apple");
+  assert_eq!(name(&&&Constants::Apple), b"Apple");
+  assert_eq!(name(&Box::new(&Constants::Apple)), b"Apple");
+
+  let boxed: Box<dyn AsCStr> = Box::new(Constants::Apple);
+  assert_eq!(name(&boxed), b"Apple");
+  assert_eq!(name(&*boxed), b"Apple");
+
+  let mauve = CStr::from_bytes_with_nul(b"mauve\0").unwrap();
+  assert_eq!(name(&&Held::Other(mauve)), b"mauve");
+  assert_eq!(name(&Box::new(Held::Red)), b"Red");
+}
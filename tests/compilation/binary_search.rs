@@ -0,0 +1,57 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+// Declared out of order, so the derive has to sort the names
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(lookup="binary_search")]
+enum Enum {
+  Zebra,
+  #[cstr(name="apple")]
+  Apple,
+  Mango,
+  #[cstr(name="Man")]
+  Man,
+  #[cstr(name="")]
+  Empty,
+  Banana = 7,
+  #[cstr(accept_ident)]
+  #[cstr(name="kiwi")]
+  Kiwi,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(lookup="binary_search")]
+enum WithDefault {
+  Yes,
+  No,
+  #[cstr(default)]
+  Unknown,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(lookup="binary_search", prefix_fallback)]
+enum WithPrefix {
+  Apple,
+  Apricot,
+}
+
+fn main() {
+  for v in [Enum::Zebra, Enum::Apple, Enum::Mango, Enum::Man, Enum::Empty, Enum::Banana, Enum::Kiwi] {
+    assert_eq!(Enum::from_cstr(v.as_cstr()), Ok(v));
+  }
+  assert_eq!(Enum::from_cstr(cstr(b"Kiwi\0")), Ok(Enum::Kiwi));
+  for miss in [&b"Apple\0"[..], b"Ma\0", b"Mangos\0", b"Zebras\0", b"\xff\0", b"A\0"] {
+    assert!(Enum::from_cstr(cstr(miss)).is_err());
+  }
+
+  assert_eq!(WithDefault::from_cstr(cstr(b"No\0")), Ok(WithDefault::No));
+  assert_eq!(WithDefault::from_cstr(cstr(b"Unknown\0")), Ok(WithDefault::Unknown));
+  assert_eq!(WithDefault::from_cstr(cstr(b"Maybe\0")), Ok(WithDefault::Unknown));
+
+  assert_eq!(WithPrefix::from_cstr(cstr(b"Apple\0")), Ok(WithPrefix::Apple));
+  assert_eq!(WithPrefix::from_cstr(cstr(b"Apr\0")), Ok(WithPrefix::Apricot));
+  assert!(WithPrefix::from_cstr(cstr(b"Ap\0")).is_err());
+}
@@ -0,0 +1,26 @@
+use cstr_enum::*;
+
+const fn pork() -> &'static [u8] {
+  b"pork\0"
+}
+
+#[derive(FromCStr)]
+#[cstr(lookup="binary_search")]
+enum NameFn {
+  #[cstr(name_fn="pork")]
+  Bacon,
+}
+
+#[derive(FromCStr)]
+#[cstr(lookup="binary_search", case_insensitive)]
+enum CaseInsensitive {
+  Apple,
+}
+
+#[derive(FromCStr)]
+#[cstr(lookup="hash")]
+enum Unknown {
+  Apple,
+}
+
+fn main() {}
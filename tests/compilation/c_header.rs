@@ -0,0 +1,24 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[cstr(emit_c_header)]
+#[allow(dead_code)]
+enum Enum0 {
+  Apple = -1,
+  #[cstr(name="pork")]
+  Bacon,
+  Cat = 1337,
+  #[cstr(name="\"tab\"\t")]
+  Dog,
+}
+
+fn main() {
+  let header = Enum0::to_c_header("enum0");
+  assert!(header.starts_with("enum enum0 {\n"));
+  assert!(header.contains("  enum0_Apple = -1,\n"));
+  assert!(header.contains("  enum0_Bacon = 0,\n"));
+  assert!(header.contains("  enum0_Cat = 1337,\n"));
+  assert!(header.contains("  enum0_Dog = 1338,\n"));
+  assert!(header.contains("static const char* enum0_names[] = {\n"));
+  assert!(header.contains("  \"Apple\",\n  \"pork\",\n  \"Cat\",\n  \"\\\"tab\\\"\\011\",\n};\n"));
+}
@@ -0,0 +1,52 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+const fn computed() -> &'static [u8] {
+  b"computed\0"
+}
+
+#[derive(AsCStr)]
+#[cstr(emit_c_module = "ffi")]
+enum FruitKind {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon,
+  Cat = 1337,
+  #[cstr(name_fn="computed")]
+  Dog,
+}
+
+#[derive(AsCStr)]
+#[cstr(emit_c_module = "meat_ffi")]
+#[allow(dead_code)]
+enum Meat {
+  Ham(u8),
+}
+
+// Link against the statics as C code would
+extern "C" {
+  static FRUIT_KIND_COUNT: usize;
+  static FRUIT_KIND_NAMES: [*const c_char; 4];
+  static FRUIT_KIND_DISCRIMINANTS: [i64; 4];
+}
+
+fn main() {
+  assert_eq!(ffi::FRUIT_KIND_COUNT, 4);
+  assert_eq!(ffi::FRUIT_KIND_NAMES.len(), FruitKind::CSTR_VARIANTS.len());
+  for (ptr, name) in ffi::FRUIT_KIND_NAMES.iter().zip(FruitKind::CSTR_VARIANTS) {
+    assert_eq!(ptr.as_cstr(), *name);
+  }
+  assert_eq!(ffi::FRUIT_KIND_DISCRIMINANTS, [0, 1, 1337, 1338]);
+  assert_eq!(FruitKind::Bacon.as_cstr(), ffi::FRUIT_KIND_NAMES[1].as_cstr());
+
+  unsafe {
+    assert_eq!(FRUIT_KIND_COUNT, 4);
+    assert_eq!(CStr::from_ptr(FRUIT_KIND_NAMES[1]).to_bytes(), b"pork");
+    assert_eq!(FRUIT_KIND_DISCRIMINANTS[2], 1337);
+    assert_eq!(CStr::from_ptr(FRUIT_KIND_NAMES[3]).to_bytes(), b"computed");
+  }
+
+  assert_eq!(meat_ffi::MEAT_COUNT, 1);
+  assert_eq!(meat_ffi::MEAT_NAMES[0].as_cstr(), Meat::Ham(0).as_cstr());
+}
@@ -0,0 +1,43 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(case_insensitive)]
+enum Enum0 {
+  #[cstr(name="foo")]
+  Foo,
+  Bar,
+  Apple,
+  #[cstr(name="Été")]
+  Summer,
+}
+
+// The same names are distinct without the flag
+#[derive(Debug, PartialEq, FromCStr)]
+enum Enum1 {
+  #[cstr(name="foo")]
+  Lower,
+  #[cstr(name="FOO")]
+  Upper,
+}
+
+fn main() {
+  assert_eq!(Enum0::from_cstr(cstr(b"FOO\0")), Ok(Enum0::Foo));
+  assert_eq!(Enum0::from_cstr(cstr(b"bAR\0")), Ok(Enum0::Bar));
+  assert!(Enum0::from_cstr(cstr(b"baz\0")).is_err());
+  for name in [&b"apple\0"[..], b"APPLE\0", b"Apple\0"] {
+    assert_eq!(Enum0::from_cstr(cstr(name)), Ok(Enum0::Apple));
+  }
+  // only ASCII case is folded
+  assert_eq!(Enum0::from_cstr(cstr("ÉTé\0".as_bytes())), Ok(Enum0::Summer));
+  assert!(Enum0::from_cstr(cstr("été\0".as_bytes())).is_err());
+
+  assert_eq!(Enum0::Bar.as_cstr(), cstr(b"Bar\0"));
+
+  assert_eq!(Enum1::from_cstr(cstr(b"foo\0")), Ok(Enum1::Lower));
+  assert_eq!(Enum1::from_cstr(cstr(b"FOO\0")), Ok(Enum1::Upper));
+  assert!(Enum1::from_cstr(cstr(b"Foo\0")).is_err());
+}
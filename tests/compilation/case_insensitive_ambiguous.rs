@@ -0,0 +1,12 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+#[cstr(case_insensitive)]
+enum Enum0 {
+  #[cstr(name="foo")]
+  Lower,
+  #[cstr(name="FOO")]
+  Upper,
+}
+
+fn main() {}
@@ -0,0 +1,30 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+// The same enum as in tests/cfg_variants.rs, which is compiled with `cfg(test)`
+#[derive(Debug, PartialEq, AsCStr, FromCStr, CStrDispatch)]
+#[cstr(emit_index)]
+enum Constants {
+  Apple,
+  #[cfg(test)]
+  #[cstr(name="mock")]
+  Mock,
+  #[cfg(not(test))]
+  Real,
+  Bacon,
+}
+
+fn main() {
+  assert_eq!(Constants::CSTR_VARIANTS, [cstr(b"Apple\0"), cstr(b"Real\0"), cstr(b"Bacon\0")]);
+  assert_eq!(Constants::Real.as_cstr(), cstr(b"Real\0"));
+  assert_eq!(Constants::Bacon.index(), 2);
+
+  assert_eq!(Constants::from_cstr(cstr(b"Real\0")), Ok(Constants::Real));
+  assert!(Constants::from_cstr(cstr(b"mock\0")).is_err());
+
+  let handlers = ConstantsHandlers { apple: || 0, real: || 1, bacon: || 2 };
+  assert_eq!(Constants::dispatch(cstr(b"Real\0"), &handlers), Some(1));
+}
@@ -0,0 +1,44 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(AsCStr)]
+enum Enum0 {
+  #[cstr(name = "start")]
+  Start,
+  #[cstr(name = "status", alias = "state")]
+  Status,
+  #[cstr(name = "stop", skip)]
+  Stop,
+  Quit,
+  Exit,
+}
+
+#[derive(AsCStr)]
+#[cstr(case_insensitive)]
+enum Enum1<T> {
+  Apple(T),
+}
+
+#[derive(AsCStr)]
+enum Empty {}
+
+fn main() {
+  assert_eq!(Enum0::closest_name(cstr(b"statsu\0")), Some(cstr(b"status\0")));
+  assert_eq!(Enum0::closest_name(cstr(b"Quit\0")), Some(cstr(b"Quit\0")));
+  // Aliases lead to the name of their variant
+  assert_eq!(Enum0::closest_name(cstr(b"stae\0")), Some(cstr(b"status\0")));
+  // Skipped variants are never suggested
+  assert_eq!(Enum0::closest_name(cstr(b"stopp\0")), None);
+  // Too far away, or as close to two names
+  assert_eq!(Enum0::closest_name(cstr(b"shutdown\0")), None);
+  assert_eq!(Enum0::closest_name(cstr(b"Exut\0")), Some(cstr(b"Exit\0")));
+  assert_eq!(Enum0::closest_name(cstr(b"Quxt\0")), Some(cstr(b"Quit\0")));
+  assert_eq!(Enum0::closest_name(cstr(b"Qxit\0")), None);
+
+  assert_eq!(Enum1::<u8>::closest_name(cstr(b"APPEL\0")), None);
+  assert_eq!(Enum1::<u8>::closest_name(cstr(b"APPL\0")), Some(cstr(b"Apple\0")));
+  assert_eq!(Empty::closest_name(cstr(b"x\0")), None);
+}
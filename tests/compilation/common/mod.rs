@@ -0,0 +1,7 @@
+//! Helpers shared by the compilation tests, which include this file with `#[path]`.
+use std::ffi::CStr;
+
+/// The C string in `bytes`, which must end with its nul terminator.
+pub fn cstr(bytes: &[u8]) -> &CStr {
+  CStr::from_bytes_with_nul(bytes).unwrap()
+}
@@ -0,0 +1,291 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(name_template = "Reg{}", concat_names)]
+enum Register {
+  Reg0,
+  Reg1,
+  Reg2,
+  Reg3,
+  Reg4,
+  Reg5,
+  Reg6,
+  Reg7,
+  Reg8,
+  Reg9,
+  Reg10,
+  Reg11,
+  Reg12,
+  Reg13,
+  Reg14,
+  Reg15,
+  Reg16,
+  Reg17,
+  Reg18,
+  Reg19,
+  Reg20,
+  Reg21,
+  Reg22,
+  Reg23,
+  Reg24,
+  Reg25,
+  Reg26,
+  Reg27,
+  Reg28,
+  Reg29,
+  Reg30,
+  Reg31,
+  Reg32,
+  Reg33,
+  Reg34,
+  Reg35,
+  Reg36,
+  Reg37,
+  Reg38,
+  Reg39,
+  Reg40,
+  Reg41,
+  Reg42,
+  Reg43,
+  Reg44,
+  Reg45,
+  Reg46,
+  Reg47,
+  Reg48,
+  Reg49,
+  Reg50,
+  Reg51,
+  Reg52,
+  Reg53,
+  Reg54,
+  Reg55,
+  Reg56,
+  Reg57,
+  Reg58,
+  Reg59,
+  Reg60,
+  Reg61,
+  Reg62,
+  Reg63,
+  Reg64,
+  Reg65,
+  Reg66,
+  Reg67,
+  Reg68,
+  Reg69,
+  Reg70,
+  Reg71,
+  Reg72,
+  Reg73,
+  Reg74,
+  Reg75,
+  Reg76,
+  Reg77,
+  Reg78,
+  Reg79,
+  Reg80,
+  Reg81,
+  Reg82,
+  Reg83,
+  Reg84,
+  Reg85,
+  Reg86,
+  Reg87,
+  Reg88,
+  Reg89,
+  Reg90,
+  Reg91,
+  Reg92,
+  Reg93,
+  Reg94,
+  Reg95,
+  Reg96,
+  Reg97,
+  Reg98,
+  Reg99,
+  Reg100,
+  Reg101,
+  Reg102,
+  Reg103,
+  Reg104,
+  Reg105,
+  Reg106,
+  Reg107,
+  Reg108,
+  Reg109,
+  Reg110,
+  Reg111,
+  Reg112,
+  Reg113,
+  Reg114,
+  Reg115,
+  Reg116,
+  Reg117,
+  Reg118,
+  Reg119,
+  Reg120,
+  Reg121,
+  Reg122,
+  Reg123,
+  Reg124,
+  Reg125,
+  Reg126,
+  Reg127,
+  Reg128,
+  Reg129,
+  Reg130,
+  Reg131,
+  Reg132,
+  Reg133,
+  Reg134,
+  Reg135,
+  Reg136,
+  Reg137,
+  Reg138,
+  Reg139,
+  Reg140,
+  Reg141,
+  Reg142,
+  Reg143,
+  Reg144,
+  Reg145,
+  Reg146,
+  Reg147,
+  Reg148,
+  Reg149,
+  Reg150,
+  Reg151,
+  Reg152,
+  Reg153,
+  Reg154,
+  Reg155,
+  Reg156,
+  Reg157,
+  Reg158,
+  Reg159,
+  Reg160,
+  Reg161,
+  Reg162,
+  Reg163,
+  Reg164,
+  Reg165,
+  Reg166,
+  Reg167,
+  Reg168,
+  Reg169,
+  Reg170,
+  Reg171,
+  Reg172,
+  Reg173,
+  Reg174,
+  Reg175,
+  Reg176,
+  Reg177,
+  Reg178,
+  Reg179,
+  Reg180,
+  Reg181,
+  Reg182,
+  Reg183,
+  Reg184,
+  Reg185,
+  Reg186,
+  Reg187,
+  Reg188,
+  Reg189,
+  Reg190,
+  Reg191,
+  Reg192,
+  Reg193,
+  Reg194,
+  Reg195,
+  Reg196,
+  Reg197,
+  Reg198,
+  Reg199,
+  Reg200,
+  Reg201,
+  Reg202,
+  Reg203,
+  Reg204,
+  Reg205,
+  Reg206,
+  Reg207,
+  Reg208,
+  Reg209,
+  Reg210,
+  Reg211,
+  Reg212,
+  Reg213,
+  Reg214,
+  Reg215,
+  Reg216,
+  Reg217,
+  Reg218,
+  Reg219,
+  Reg220,
+  Reg221,
+  Reg222,
+  Reg223,
+  Reg224,
+  Reg225,
+  Reg226,
+  Reg227,
+  Reg228,
+  Reg229,
+  Reg230,
+  Reg231,
+  Reg232,
+  Reg233,
+  Reg234,
+  Reg235,
+  Reg236,
+  Reg237,
+  Reg238,
+  Reg239,
+  Reg240,
+  Reg241,
+  Reg242,
+  Reg243,
+  Reg244,
+  Reg245,
+  Reg246,
+  Reg247,
+  Reg248,
+  Reg249,
+  Reg250,
+  Reg251,
+  Reg252,
+  Reg253,
+  Reg254,
+  Reg255,
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(concat_names)]
+enum Mixed {
+  Apple,
+  #[cstr(name = "")]
+  Empty,
+  #[cstr(name = "pork")]
+  Bacon,
+}
+
+fn main() {
+  assert_eq!(Register::Reg0.as_cstr(), cstr(b"Reg0\0"));
+  assert_eq!(Register::Reg255.as_cstr(), cstr(b"Reg255\0"));
+  assert_eq!(Register::CSTR_VARIANTS.len(), 256);
+  assert_eq!(Register::CSTR_VARIANTS[17], cstr(b"Reg17\0"));
+  assert_eq!(Register::from_cstr(cstr(b"Reg0\0")), Ok(Register::Reg0));
+  assert_eq!(Register::from_cstr(cstr(b"Reg255\0")), Ok(Register::Reg255));
+  assert!(Register::from_cstr(cstr(b"Reg256\0")).is_err());
+
+  assert_eq!(Mixed::Apple.as_cstr(), cstr(b"Apple\0"));
+  assert_eq!(Mixed::Empty.as_cstr(), cstr(b"\0"));
+  assert_eq!(Mixed::Bacon.as_cstr(), cstr(b"pork\0"));
+  assert_eq!(Mixed::from_cstr(cstr(b"pork\0")), Ok(Mixed::Bacon));
+}
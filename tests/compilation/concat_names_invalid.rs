@@ -0,0 +1,14 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+const PORK: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"pork\0") };
+
+#[derive(AsCStr)]
+#[cstr(concat_names)]
+enum Enum {
+  Apple,
+  #[cstr(cstr = "PORK")]
+  Bacon,
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use cstr_enum::*;
+
+const fn pork() -> &'static [u8] {
+  b"pork\0"
+}
+
+#[derive(AsCStr)]
+enum Enum {
+  Apple,
+  #[cstr(name_fn="pork")]
+  Bacon,
+  #[cstr(name="cat")]
+  Cat { lives: u8 },
+}
+
+const APPLE: bool = Enum::Apple.is(b"Apple");
+const _: () = assert!(Enum::Bacon.is(b"pork"));
+const _: () = assert!(!Enum::Bacon.is(b"pork\0"));
+
+const fn is_cat(e: &Enum) -> bool {
+  e.is(b"cat")
+}
+
+#[derive(AsCStr)]
+enum Empty {}
+
+fn main() {
+  assert!(APPLE);
+  assert!(!Enum::Apple.is(b"apple"));
+  assert!(!Enum::Apple.is(b"Appl"));
+  assert!(is_cat(&Enum::Cat { lives: 9 }));
+  let _ = |e: Empty| e.is(b"");
+}
@@ -0,0 +1,32 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+mod ffi {
+  use std::ffi::CStr;
+  pub const APPLE: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"apple\0") };
+}
+
+const BACON: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"pork\0") };
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(case_insensitive)]
+enum Enum {
+  #[cstr(cstr="ffi::APPLE")]
+  Apple,
+  #[cstr(cstr="BACON")]
+  Bacon,
+  Cat,
+}
+
+fn main() {
+  assert!(std::ptr::eq(Enum::Apple.as_cstr(), ffi::APPLE));
+  assert_eq!(Enum::Bacon.as_cstr(), BACON);
+  assert_eq!(Enum::CSTR_VARIANTS, [ffi::APPLE, BACON, Enum::Cat.as_cstr()]);
+  assert_eq!(Enum::CSTR_NAME_MAX_LEN, 5);
+
+  for v in [Enum::Apple, Enum::Bacon, Enum::Cat] {
+    assert_eq!(Enum::from_cstr(v.as_cstr()), Ok(v));
+  }
+  assert_eq!(Enum::from_cstr(CStr::from_bytes_with_nul(b"PORK\0").unwrap()), Ok(Enum::Bacon));
+  assert!(Enum::from_cstr(CStr::from_bytes_with_nul(b"Bacon\0").unwrap()).is_err());
+}
@@ -0,0 +1,17 @@
+use cstr_enum::*;
+
+const BYTES: &[u8] = b"apple\0";
+
+#[derive(AsCStr)]
+enum Enum {
+  #[cstr(cstr="BYTES")]
+  Apple,
+}
+
+#[derive(AsCStr)]
+enum Both {
+  #[cstr(name="apple", cstr="BYTES")]
+  Apple,
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+use cstr_enum::*;
+use std::ffi::CString;
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Enum {
+  Apple,
+  Bacon,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(unit_error)]
+enum Unit {
+  Apple,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Capture {
+  Apple,
+  #[cstr(other)]
+  Other(CString),
+}
+
+fn main() {
+  let record = b"Apple\x7fBacon\0\0\0unknown\0";
+  assert_eq!(Enum::from_cstr_field(record, 0, 5), Ok(Enum::Apple));
+  assert_eq!(Enum::from_cstr_field(record, 6, 8), Ok(Enum::Bacon));
+  assert_eq!(Enum::from_cstr_field(record, 6, 5), Ok(Enum::Bacon));
+  assert!(Enum::from_cstr_field(record, 0, 6).is_err());
+  assert!(Enum::from_cstr_field(record, 6, 0).is_err());
+
+  let unknown = Enum::from_cstr_field(record, 14, 8).unwrap_err();
+  let out_of_range = Enum::from_cstr_field(record, 14, 9).unwrap_err();
+  assert_ne!(unknown, out_of_range);
+  assert_eq!(Enum::from_cstr_field(record, usize::MAX, 2).unwrap_err(), out_of_range);
+  assert_eq!(Enum::from_cstr_field(record, record.len() + 1, 0).unwrap_err(), out_of_range);
+  assert_ne!(Enum::from_cstr_field(record, record.len(), 0).unwrap_err(), out_of_range);
+
+  assert_eq!(Unit::from_cstr_field(record, 0, 5), Ok(Unit::Apple));
+  assert_eq!(Unit::from_cstr_field(record, 0, 500), Err(()));
+
+  assert_eq!(Capture::from_cstr_field(record, 14, 8), Ok(Capture::Other(CString::new("unknown").unwrap())));
+}
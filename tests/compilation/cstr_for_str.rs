@@ -0,0 +1,35 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+const fn cat() -> &'static [u8] {
+  b"cat\0"
+}
+
+#[derive(AsCStr)]
+enum Enum<'a> {
+  Apple,
+  #[cstr(name="pork", accept_ident)]
+  Bacon,
+  #[cstr(name_fn="cat")]
+  Cat { lives: u8 },
+  #[cstr(transparent)]
+  Held(&'a CStr),
+}
+
+#[derive(AsCStr)]
+#[cstr(case_insensitive)]
+enum Insensitive {
+  Apple,
+}
+
+fn main() {
+  assert!(std::ptr::eq(Enum::cstr_for_str("Apple").unwrap(), Enum::Apple.as_cstr()));
+  assert_eq!(Enum::cstr_for_str("pork").unwrap().to_bytes(), b"pork");
+  assert_eq!(Enum::cstr_for_str("Bacon").unwrap().to_bytes(), b"pork");
+  assert_eq!(Enum::cstr_for_str("cat").unwrap().to_bytes(), b"cat");
+  assert_eq!(Enum::cstr_for_str("apple"), None);
+  assert_eq!(Enum::cstr_for_str("Held"), None);
+  assert_eq!(Enum::cstr_for_str(""), None);
+
+  assert_eq!(Insensitive::cstr_for_str("APPLE").unwrap().to_bytes(), b"Apple");
+}
@@ -0,0 +1,29 @@
+use cstr_enum::*;
+use std::collections::HashMap;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, AsCStr)]
+enum Enum0 {
+  Apple,
+  #[cstr(name = "pork", alias = "bacon")]
+  Bacon,
+  Cat = 1337,
+}
+
+#[derive(AsCStr)]
+enum Empty {}
+
+fn main() {
+  let pairs: Vec<_> = Enum0::cstr_variants().collect();
+  assert_eq!(pairs, [(Enum0::Apple, cstr(b"Apple\0")), (Enum0::Bacon, cstr(b"pork\0")), (Enum0::Cat, cstr(b"Cat\0"))]);
+
+  // Both directions of a lookup table at once
+  let (by_variant, by_name): (HashMap<_, _>, HashMap<_, _>) = Enum0::cstr_variants().map(|(v, name)| ((v, name), (name, v))).unzip();
+  assert_eq!(by_variant[&Enum0::Bacon], cstr(b"pork\0"));
+  assert_eq!(by_name[cstr(b"Cat\0")], Enum0::Cat);
+
+  assert_eq!(Empty::cstr_variants().count(), 0);
+}
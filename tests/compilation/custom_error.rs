@@ -0,0 +1,36 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+mod errors {
+  #[derive(Debug, Clone, PartialEq)]
+  pub struct MyError(pub String);
+
+  impl From<&'static str> for MyError {
+    fn from(msg: &'static str) -> Self {
+      MyError(msg.to_owned())
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, FromCStr, FromCWStr)]
+#[cstr(error = "crate::errors::MyError")]
+enum Constants {
+  Apple,
+}
+
+fn main() {
+  let unknown = errors::MyError("unexpected string while parsing for Constants variant".to_owned());
+  assert_eq!(Constants::from_cstr(cstr(b"Apple\0")), Ok(Constants::Apple));
+  assert_eq!(Constants::from_cstr(cstr(b"Banana\0")), Err(unknown.clone()));
+  assert_eq!(Constants::from_cstr(cstr(b"\0")), Err(unknown.clone()));
+  assert_eq!(Constants::from_byte_iter(b"Bananas".iter().copied()), Err(unknown.clone()));
+  assert_eq!(Constants::from_cwstr(&[b'B' as u16, 0]), Err(unknown.clone()));
+  assert_eq!(
+    Constants::from_cstr_field(b"Apple", 2, 10),
+    Err(errors::MyError("field out of range while parsing for Constants variant".to_owned()))
+  );
+  let _: Result<Constants, errors::MyError> = Constants::from_cstr(cstr(b"Apple\0"));
+}
@@ -0,0 +1,17 @@
+use cstr_enum::*;
+
+struct NoFrom;
+
+#[derive(FromCStr)]
+#[cstr(error = "NoFrom", unit_error)]
+enum Enum0 {
+  Apple,
+}
+
+#[derive(FromCStr)]
+#[cstr(error = "NoFrom")]
+enum Enum1 {
+  Apple,
+}
+
+fn main() {}
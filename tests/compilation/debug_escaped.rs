@@ -0,0 +1,25 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+#[derive(AsCStr, CStrDebugEscaped)]
+enum Enum<'a> {
+  Apple,
+  #[cstr(name="tab\there")]
+  Tab,
+  #[cstr(name="\x01control\x7f")]
+  Control,
+  #[cstr(name="quote\"back\\slash")]
+  Quote,
+  #[cstr(transparent)]
+  Held(&'a CStr),
+}
+
+fn main() {
+  assert_eq!(format!("{:?}", Enum::Apple), "Apple");
+  assert_eq!(format!("{:?}", Enum::Tab), r"tab\there");
+  assert_eq!(format!("{:?}", Enum::Control), r"\x01control\x7f");
+  assert_eq!(format!("{:?}", Enum::Quote), r#"quote\"back\\slash"#);
+  let held = CStr::from_bytes_with_nul(b"\xffheld\0").unwrap();
+  assert_eq!(format!("{:?}", Enum::Held(held)), r"\xffheld");
+  assert_eq!(format!("{:?}", [Enum::Apple, Enum::Tab]), r"[Apple, tab\there]");
+}
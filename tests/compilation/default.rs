@@ -0,0 +1,20 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+enum Enum0 {
+  Apple,
+  #[cstr(default, name="unknown")]
+  Unknown,
+}
+
+fn main() {
+  assert_eq!(Enum0::from_cstr(cstr(b"Apple\0")), Ok(Enum0::Apple));
+  assert_eq!(Enum0::from_cstr(cstr(b"unknown\0")), Ok(Enum0::Unknown));
+  assert_eq!(Enum0::from_cstr(cstr(b"Banana\0")), Ok(Enum0::Unknown));
+  assert_eq!(Enum0::from_byte_iter(b"much too long".iter().copied()), Ok(Enum0::Unknown));
+  assert_eq!(Enum0::Unknown.as_cstr(), cstr(b"unknown\0"));
+}
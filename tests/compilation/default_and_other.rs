@@ -0,0 +1,11 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+enum Enum0 {
+  #[cstr(default)]
+  Unknown,
+  #[cstr(other)]
+  Other,
+}
+
+fn main() {}
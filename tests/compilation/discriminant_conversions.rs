@@ -0,0 +1,27 @@
+use cstr_enum::*;
+
+#[derive(Debug, PartialEq, AsCStr)]
+#[cstr(emit_discriminant_conversions)]
+enum Enum0 {
+  Apple,
+  Bacon,
+  Cat = 1337,
+  Dog,
+  Egg = -5,
+}
+
+const CAT: Option<Enum0> = Enum0::try_from_i64(1337);
+
+fn main() {
+  assert_eq!(i64::from(Enum0::Apple), 0);
+  assert_eq!(i64::from(Enum0::Bacon), 1);
+  assert_eq!(i64::from(Enum0::Cat), 1337);
+  assert_eq!(i64::from(Enum0::Dog), 1338);
+  assert_eq!(i64::from(Enum0::Egg), -5);
+
+  assert_eq!(CAT, Some(Enum0::Cat));
+  assert_eq!(Enum0::try_from_i64(1338), Some(Enum0::Dog));
+  assert_eq!(Enum0::try_from_i64(-5), Some(Enum0::Egg));
+  assert_eq!(Enum0::try_from_i64(2), None);
+  assert_eq!(Enum0::try_from_i64(i64::MAX), None);
+}
@@ -0,0 +1,29 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(CStrDispatch)]
+enum Command {
+  Start,
+  #[cstr(name="halt")]
+  Stop,
+  ResetAll,
+  Type,
+}
+
+fn main() {
+  let handlers = CommandHandlers {
+    start: || 1,
+    stop: || 2,
+    reset_all: || 3,
+    r#type: || 4,
+  };
+  assert_eq!(Command::dispatch(cstr(b"Start\0"), &handlers), Some(1));
+  assert_eq!(Command::dispatch(cstr(b"halt\0"), &handlers), Some(2));
+  assert_eq!(Command::dispatch(cstr(b"ResetAll\0"), &handlers), Some(3));
+  assert_eq!(Command::dispatch(cstr(b"Type\0"), &handlers), Some(4));
+  assert_eq!(Command::dispatch(cstr(b"Stop\0"), &handlers), None);
+  assert_eq!(Command::dispatch(cstr(b"unknown\0"), &handlers), None);
+}
@@ -0,0 +1,25 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+#[derive(AsCStr, CStrDisplay)]
+enum Enum<'a> {
+  Apple,
+  #[cstr(name="Été")]
+  Summer,
+  #[cstr(name="日本")]
+  Japan,
+  #[cstr(name=b"\xFFraw\xC3")]
+  Raw,
+  #[cstr(transparent)]
+  Held(&'a CStr),
+}
+
+fn main() {
+  assert_eq!(Enum::Apple.to_string(), "Apple");
+  assert_eq!(format!("{}", Enum::Summer), "Été");
+  assert_eq!(format!("[{}]", Enum::Japan), "[日本]");
+  assert_eq!(Enum::Raw.to_string(), "\u{FFFD}raw\u{FFFD}");
+  let held = CStr::from_bytes_with_nul("héld\0".as_bytes()).unwrap();
+  assert_eq!(Enum::Held(held).to_string(), "héld");
+  assert_eq!(Enum::Held(held).to_string(), String::from_utf8_lossy(Enum::Held(held).as_cstr().to_bytes()));
+}
@@ -0,0 +1,28 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(allow_duplicate_emit)]
+enum Enum {
+  #[cstr(name = "X")]
+  Old,
+  #[cstr(name = "X", canonical)]
+  Current,
+  Other,
+  #[cstr(name = "Y", canonical)]
+  Newest,
+  #[cstr(name = "Y")]
+  Older,
+}
+
+fn main() {
+  assert_eq!(Enum::Old.as_cstr(), cstr(b"X\0"));
+  assert_eq!(Enum::Current.as_cstr(), cstr(b"X\0"));
+  assert_eq!(Enum::from_cstr(cstr(b"X\0")), Ok(Enum::Current));
+  assert_eq!(Enum::from_cstr(cstr(b"Y\0")), Ok(Enum::Newest));
+  assert_eq!(Enum::from_cstr(cstr(b"Other\0")), Ok(Enum::Other));
+  assert!(Enum::from_cstr(cstr(b"Old\0")).is_err());
+}
@@ -0,0 +1,45 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+#[cstr(allow_duplicate_emit)]
+enum Enum0 {
+  #[cstr(name = "X")]
+  Old,
+  #[cstr(name = "X")]
+  Current,
+}
+
+#[derive(FromCStr)]
+#[cstr(allow_duplicate_emit)]
+enum Enum1 {
+  #[cstr(name = "X", canonical)]
+  Old,
+  #[cstr(name = "X", canonical)]
+  Current,
+}
+
+#[derive(FromCStr)]
+#[cstr(allow_duplicate_emit)]
+enum Enum2 {
+  #[cstr(canonical)]
+  Unique,
+}
+
+#[derive(FromCStr)]
+enum Enum3 {
+  #[cstr(name = "X")]
+  Old,
+  #[cstr(name = "X", canonical)]
+  Current,
+}
+
+#[derive(FromCStr)]
+#[cstr(allow_duplicate_emit)]
+enum Enum4 {
+  #[cstr(name = "X", alias = "x")]
+  Old,
+  #[cstr(name = "X", canonical)]
+  Current,
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+enum Enum0 {
+  #[cstr(name = "apple")]
+  Apple,
+  #[cstr(name = "apple")]
+  Pear,
+}
+
+#[derive(AsCStr)]
+#[cstr(rename_all = "snake_case")]
+#[allow(non_camel_case_types)]
+enum Enum1 {
+  HttpServer,
+  Http_Server,
+}
+
+#[derive(AsCStr)]
+enum Enum2 {
+  Apple,
+  #[cstr(name = "Apple")]
+  Pear,
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use cstr_enum::*;
+
+#[derive(Debug, PartialEq, AsCStr)]
+#[cstr(emit_index)]
+enum Enum0 {
+  Apple = 3,
+  Bacon,
+  Cat = 1337,
+  Dog = 2,
+}
+
+const CAT_INDEX: usize = Enum0::Cat.index();
+
+fn main() {
+  assert_eq!(CAT_INDEX, 2);
+  assert_eq!(Enum0::Apple.index(), 0);
+  assert_eq!(Enum0::Bacon.index(), 1);
+  assert_eq!(Enum0::Dog.index(), 3);
+  assert_eq!(Enum0::Cat as usize, 1337);
+
+  assert_eq!(Enum0::from_index(2), Some(Enum0::Cat));
+  assert_eq!(Enum0::from_index(4), None);
+  assert_eq!(Enum0::from_index(1337), None);
+
+  for (i, name) in Enum0::CSTR_VARIANTS.iter().enumerate() {
+    assert_eq!(Enum0::from_index(i).unwrap().as_cstr(), *name);
+  }
+}
@@ -0,0 +1,48 @@
+use cstr_enum::*;
+
+const fn kilo() -> &'static [u8] {
+  b"kilo\0"
+}
+
+#[derive(AsCStr)]
+#[cstr(emit_lengths)]
+enum Constants {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon { grams: u32 },
+  #[cstr(name="")]
+  Empty,
+  #[cstr(name="Été")]
+  Summer,
+  #[cstr(name_fn="kilo")]
+  Kilo,
+  VariantLens,
+}
+
+#[derive(AsCStr)]
+#[cstr(emit_lengths, concat_names)]
+enum Packed {
+  A,
+  Bb,
+  Ccc,
+}
+
+const FRAME: [u8; Packed::CSTR_VARIANT_LENS[2]] = *b"Ccc";
+
+fn main() {
+  let all = [
+    Constants::Apple,
+    Constants::Bacon { grams: 1 },
+    Constants::Empty,
+    Constants::Summer,
+    Constants::Kilo,
+    Constants::VariantLens,
+  ];
+  assert_eq!(Constants::CSTR_VARIANT_LENS.len(), all.len());
+  for (v, &len) in all.iter().zip(&Constants::CSTR_VARIANT_LENS) {
+    assert_eq!(v.as_cstr().to_bytes().len(), len);
+  }
+  assert_eq!(Constants::CSTR_VARIANT_LENS, [5, 4, 0, 5, 4, 11]);
+  assert_eq!(Packed::CSTR_VARIANT_LENS, [1, 2, 3]);
+  assert_eq!(&FRAME, Packed::Ccc.as_cstr().to_bytes());
+}
@@ -0,0 +1,27 @@
+use cstr_enum::*;
+
+#[derive(Debug, PartialEq, AsCStr)]
+#[cstr(emit_metadata, rename_all = "snake_case")]
+enum Enum0 {
+  SmokedApple = 3,
+  #[cstr(name = "pork")]
+  Bacon,
+  Cat = -1337,
+  Mapping = 10,
+  Discriminants,
+}
+
+fn main() {
+  assert_eq!(Enum0::CSTR_MAPPING.len(), Enum0::CSTR_VARIANTS.len());
+  assert_eq!(Enum0::CSTR_DISCRIMINANTS, [3, 4, -1337, 10, 11]);
+  assert_eq!(Enum0::CSTR_MAPPING[0].0, "SmokedApple");
+  assert_eq!(Enum0::CSTR_MAPPING[1].1, Enum0::Bacon.as_cstr());
+
+  let header = Enum0::to_c_header("e");
+  for ((ident, name), (discriminant, listed)) in Enum0::CSTR_MAPPING.iter().zip(Enum0::CSTR_DISCRIMINANTS.iter().zip(Enum0::CSTR_VARIANTS)) {
+    assert_eq!(name, listed);
+    assert!(header.contains(&format!("e_{} = {},", ident, discriminant)));
+    assert!(header.contains(&format!("\"{}\",", name.to_str().unwrap())));
+  }
+  assert_eq!(Enum0::CSTR_SMOKED_APPLE.to_bytes(), b"smoked_apple");
+}
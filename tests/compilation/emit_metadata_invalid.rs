@@ -0,0 +1,10 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[cstr(emit_metadata)]
+enum Enum0 {
+  Apple,
+  Bacon(u32),
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+#[derive(AsCStr, FromCStr, AsCWStr, FromCWStr, CStrDispatch, CStrDebugEscaped, CStrDisplay)]
+#[cstr(emit_index, emit_discriminant_conversions, emit_c_module = "ffi")]
+enum Never {}
+
+fn main() {
+  let _ = |n: &Never| n.as_cstr().to_owned();
+  let _ = |n: &Never| n.as_cwstr().to_owned();
+  assert!(Never::from_cstr(CStr::from_bytes_with_nul(b"\0").unwrap()).is_err());
+  assert!(Never::from_cstr(CStr::from_bytes_with_nul(b"Apple\0").unwrap()).is_err());
+  assert!(Never::from_cwstr(&[0]).is_err());
+  assert!(Never::from_byte_iter(b"Apple".iter().copied()).is_err());
+  assert!(Never::CSTR_VARIANTS.is_empty());
+  assert_eq!(Never::CSTR_NAME_MAX_LEN, 0);
+  assert!(Never::from_index(0).is_none());
+  assert!(Never::try_from_i64(0).is_none());
+  assert!(Never::cstr_for_str("Apple").is_none());
+  assert!(Never::dispatch(CStr::from_bytes_with_nul(b"Apple\0").unwrap(), &NeverHandlers::<()> { returns: std::marker::PhantomData }).is_none());
+}
@@ -0,0 +1,35 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, FromCWStr)]
+enum Enum {
+  Apple,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(name_template="{}")]
+enum Numbered {
+  Zero,
+}
+
+fn main() {
+  assert_eq!(Enum::from_cstr(cstr(b"Apple\0")), Ok(Enum::Apple));
+  let empty = Enum::from_cstr(cstr(b"\0")).unwrap_err();
+  let unknown = Enum::from_cstr(cstr(b"Bacon\0")).unwrap_err();
+  assert_eq!(Enum::from_byte_iter(b"very long input".iter().copied()).unwrap_err(), unknown);
+  assert_eq!(Enum::from_cwstr(&[0]).unwrap_err(), empty);
+  assert_eq!(Enum::from_cwstr(&[66, 0]).unwrap_err(), unknown);
+  assert_eq!(Numbered::from_cstr(cstr(b"0\0")), Ok(Numbered::Zero));
+
+  #[cfg(not(feature = "rich_error"))]
+  assert_eq!(empty, unknown);
+  #[cfg(feature = "rich_error")]
+  {
+    assert!(empty.is_empty_input());
+    assert!(!unknown.is_empty_input());
+    assert_eq!(empty.to_string(), "empty string while parsing for Enum variant");
+  }
+}
@@ -0,0 +1,27 @@
+use cstr_enum::*;
+use std::ffi::CString;
+
+#[derive(Debug, PartialEq, AsCStr)]
+enum Enum0 {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon,
+  Cat = 1337,
+}
+
+#[derive(AsCStr)]
+enum Empty {}
+
+fn main() {
+  let mut names: Vec<CString> = Vec::new();
+  for_each_variant!(Enum0, |v| names.push(v.as_cstr().to_owned()));
+  assert_eq!(names, Enum0::CSTR_VARIANTS);
+
+  let mut variants = Vec::new();
+  for_each_variant!(Enum0, |v| variants.push(v));
+  assert_eq!(variants, [Enum0::Apple, Enum0::Bacon, Enum0::Cat]);
+
+  let mut count = 0;
+  for_each_variant!(Empty, |_| count += 1);
+  assert_eq!(count, 0);
+}
@@ -0,0 +1,24 @@
+use cstr_enum::*;
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Enum {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon,
+  Cat,
+}
+
+fn main() {
+  assert_eq!(Enum::CSTR_NAME_MAX_LEN, 5);
+
+  assert_eq!(Enum::from_byte_iter(b"pork".to_vec()), Ok(Enum::Bacon));
+  assert_eq!(Enum::from_byte_iter(b"Apple\0trailing".to_vec()), Ok(Enum::Apple));
+  assert_eq!(Enum::from_byte_iter(b"Cat\0".iter().copied()), Ok(Enum::Cat));
+  assert!(Enum::from_byte_iter(b"Bacon".to_vec()).is_err());
+  assert!(Enum::from_byte_iter(Vec::new()).is_err());
+
+  // too long to match, and the iterator is not consumed any further once the buffer is full
+  let mut iter = b"Apples and pears".iter().copied();
+  assert!(Enum::from_byte_iter(&mut iter).is_err());
+  assert_eq!(iter.next(), Some(b' '));
+}
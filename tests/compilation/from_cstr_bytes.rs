@@ -0,0 +1,37 @@
+use cstr_enum::*;
+use std::ffi::{CStr, CString};
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(unit_error)]
+enum Plain {
+  Apple,
+  #[cstr(name="pear", alias="poire")]
+  Pear,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(case_insensitive)]
+enum Captured {
+  Apple,
+  #[cstr(other)]
+  Other(CString),
+}
+
+fn main() {
+  assert_eq!(Plain::from_cstr_bytes(b"Apple"), Ok(Plain::Apple));
+  assert_eq!(Plain::from_cstr_bytes(b"poire"), Ok(Plain::Pear));
+  assert_eq!(Plain::from_cstr_bytes(b""), Err(()));
+  // The nul terminator is not part of the name
+  assert_eq!(Plain::from_cstr_bytes(b"pear\0"), Err(()));
+  assert_eq!(Plain::from_cstr_bytes(b"pe\0ar"), Err(()));
+
+  for name in [&b"Apple"[..], b"pear", b"plum"] {
+    let cstr = CString::new(name).unwrap();
+    assert_eq!(Plain::from_cstr_bytes(name), Plain::from_cstr(&cstr));
+  }
+
+  assert_eq!(Captured::from_cstr_bytes(b"APPLE"), Ok(Captured::Apple));
+  assert_eq!(Captured::from_cstr_bytes(b"plum"), Ok(Captured::Other(CString::new("plum").unwrap())));
+  assert_eq!(Captured::from_cstr_bytes(b"pl\0um"), Ok(Captured::Other(CString::new("pl").unwrap())));
+  assert_eq!(Captured::from_cstr(CStr::from_bytes_with_nul(b"plum\0").unwrap()), Captured::from_cstr_bytes(b"plum"));
+}
@@ -0,0 +1,40 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Command {
+  Start,
+  Stop,
+  #[cstr(alias="stat")]
+  Status,
+  #[cstr(other)]
+  Other,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(case_insensitive)]
+enum Colour {
+  Red,
+  Green,
+}
+
+fn main() {
+  // Exact matches
+  assert_eq!(Command::from_cstr_fuzzy(cstr(b"Stop\0"), 0), Some(Command::Stop));
+  assert_eq!(Command::from_cstr_fuzzy(cstr(b"stat\0"), 3), Some(Command::Status));
+  // Typos within the threshold
+  assert_eq!(Command::from_cstr_fuzzy(cstr(b"Statsu\0"), 2), Some(Command::Status));
+  assert_eq!(Command::from_cstr_fuzzy(cstr(b"Starf\0"), 1), Some(Command::Start));
+  assert_eq!(Command::from_cstr_fuzzy(cstr(b"Statsu\0"), 1), None);
+  // `Sta` is two edits from `Start`, `Stop` and the alias of `Status`
+  assert_eq!(Command::from_cstr_fuzzy(cstr(b"Sta\0"), 2), None);
+  // `Other` has no name to be close to
+  assert_eq!(Command::from_cstr_fuzzy(cstr(b"Other\0"), 0), None);
+  assert_eq!(Command::from_cstr_fuzzy(cstr(b"\0"), 3), None);
+
+  assert_eq!(Colour::from_cstr_fuzzy(cstr(b"GREN\0"), 1), Some(Colour::Green));
+  assert_eq!(Colour::from_cstr_fuzzy(cstr(b"rde\0"), 2), Some(Colour::Red));
+}
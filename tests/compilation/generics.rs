@@ -0,0 +1,49 @@
+use cstr_enum::*;
+use std::borrow::Cow;
+use std::ffi::CStr;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, AsCStr, AsCWStr)]
+enum Wrapper<T> {
+  A(T),
+  #[cstr(name="b")]
+  B,
+}
+
+#[derive(Debug, PartialEq, AsCStr)]
+enum Borrowed<'a> {
+  Ref(&'a str),
+  Empty,
+}
+
+#[derive(Debug, PartialEq, AsCStr)]
+enum Bounded<T: Clone> where T: Default {
+  Value(T),
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+enum Captured<'a> {
+  Known,
+  #[cstr(other)]
+  Other(Cow<'a, CStr>),
+}
+
+fn main() {
+  assert_eq!(Wrapper::A(1u8).as_cstr(), cstr(b"A\0"));
+  assert_eq!(Wrapper::<String>::B.as_cstr(), cstr(b"b\0"));
+  assert_eq!(Wrapper::<()>::CSTR_VARIANTS, [cstr(b"A\0"), cstr(b"b\0")]);
+  assert_eq!(Wrapper::<()>::B.as_cwstr(), [b'b' as u16, 0]);
+
+  let s = String::from("borrowed");
+  assert_eq!(Borrowed::Ref(&s).as_cstr(), cstr(b"Ref\0"));
+  assert_eq!(Borrowed::Empty.as_cstr(), cstr(b"Empty\0"));
+
+  assert_eq!(Bounded::Value(0i32).as_cstr(), cstr(b"Value\0"));
+
+  assert_eq!(Captured::from_cstr(cstr(b"Known\0")), Ok(Captured::Known));
+  assert_eq!(Captured::from_cstr(cstr(b"x\0")), Ok(Captured::Other(Cow::Borrowed(cstr(b"x\0")))));
+  assert_eq!(Captured::Other(Cow::Borrowed(cstr(b"x\0"))).as_cstr(), cstr(b"Other\0"));
+}
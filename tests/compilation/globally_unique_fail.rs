@@ -0,0 +1,20 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+enum Enum0 {
+  A,
+  B,
+}
+
+#[derive(AsCStr)]
+enum Enum1 {
+  C,
+  #[cstr(name="B")]
+  D,
+}
+
+assert_globally_unique!(Enum0, Enum1);
+
+fn main() {
+
+}
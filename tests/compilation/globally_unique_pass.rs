@@ -0,0 +1,23 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[allow(dead_code)]
+enum Enum0 {
+  A,
+  B,
+}
+
+#[derive(AsCStr)]
+#[allow(dead_code)]
+enum Enum1 {
+  C,
+  #[cstr(name="D")]
+  A,
+}
+
+assert_globally_unique!(Enum0, Enum1);
+
+fn main() {
+  assert_eq!(Enum0::CSTR_VARIANTS.len(), 2);
+  assert_eq!(Enum1::CSTR_VARIANTS[1].to_bytes(), b"D");
+}
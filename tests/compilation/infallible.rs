@@ -0,0 +1,19 @@
+use cstr_enum::*;
+use std::convert::Infallible;
+use std::ffi::CStr;
+
+#[derive(AsCStr)]
+enum Constants {
+  Apple,
+}
+
+fn name_of<'a, T: AsCStr>(value: Result<&'a Constants, &'a T>) -> &'a CStr {
+  match value {
+    Ok(c) => c.as_cstr(),
+    Err(e) => e.as_cstr(),
+  }
+}
+
+fn main() {
+  assert_eq!(name_of::<Infallible>(Ok(&Constants::Apple)).to_bytes(), b"Apple");
+}
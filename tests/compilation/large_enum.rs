@@ -0,0 +1,51 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+// The same names in an enum large enough to dispatch on the length of the input, and one small enough not to
+macro_rules! enums {
+  ($( $v:ident = $name:literal ),*) => {
+    #[derive(Debug, PartialEq, Clone, Copy, FromCStr, AsCStr)]
+    enum Large {
+      $( #[cstr(name = $name)] $v, )*
+    }
+
+    #[derive(Debug, PartialEq, FromCStr, AsCStr)]
+    enum Small {
+      $( #[cstr(name = $name)] $v, )*
+    }
+  };
+}
+
+enums! {
+  A = "", B = "a", C = "b", D = "ab", E = "ba", F = "abc", G = "abd", H = "bcd",
+  I = "abcd", J = "dcba", K = "hello", L = "world", M = "apple", N = "banana", O = "cherry", P = "durian",
+  Q = "elderberry", R = "fig", S = "grape", T = "a longer name than the others"
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Few {
+  #[cstr(name = "abc")]
+  Abc,
+  #[cstr(name = "ab")]
+  Ab,
+}
+
+fn main() {
+  let inputs: &[&[u8]] = &[
+    b"\0", b"a\0", b"b\0", b"c\0", b"ab\0", b"ba\0", b"aa\0", b"abc\0", b"abd\0", b"abe\0", b"bcd\0",
+    b"abcd\0", b"dcba\0", b"hello\0", b"world\0", b"worlf\0", b"apple\0", b"banana\0", b"cherry\0", b"durian\0",
+    b"elderberry\0", b"fig\0", b"grape\0", b"a longer name than the others\0", b"a longer name than the other\0",
+    b"unknown\0", b"Apple\0",
+  ];
+  for input in inputs {
+    let s = CStr::from_bytes_with_nul(input).unwrap();
+    let large = Large::from_cstr(s).map(|v| v.as_cstr().to_owned()).ok();
+    let small = Small::from_cstr(s).map(|v| v.as_cstr().to_owned()).ok();
+    assert_eq!(large, small, "{:?}", s);
+  }
+  for v in [Large::A, Large::F, Large::K, Large::T] {
+    assert_eq!(Large::from_cstr(v.as_cstr()), Ok(v));
+  }
+  assert!(Large::from_cstr(CStr::from_bytes_with_nul(b"unknown\0").unwrap()).is_err());
+  assert_eq!(Few::from_cstr(CStr::from_bytes_with_nul(b"ab\0").unwrap()), Ok(Few::Ab));
+}
@@ -0,0 +1,22 @@
+use cstr_enum::*;
+use std::collections::HashMap;
+use std::ffi::CStr;
+
+#[derive(AsCStr)]
+#[cstr(rename_all = "snake_case")]
+enum Setting {
+  MaxRetries,
+  Timeout,
+  #[cstr(name = "verbose")]
+  Verbosity,
+}
+
+fn main() {
+  let mut config: HashMap<&'static CStr, u32> = HashMap::new();
+  config.insert(CStr::from_bytes_with_nul(b"max_retries\0").unwrap(), 3);
+  config.insert(CStr::from_bytes_with_nul(b"verbose\0").unwrap(), 1);
+
+  assert_eq!(Setting::MaxRetries.lookup_in(&config), Some(&3));
+  assert_eq!(Setting::Verbosity.lookup_in(&config), Some(&1));
+  assert_eq!(Setting::Timeout.lookup_in(&config), None);
+}
@@ -0,0 +1,42 @@
+use cstr_enum::*;
+use std::ffi::CString;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(accept_ident)]
+enum Constants {
+  #[cstr(name = "apple", alias = "aple", alias(value = "APPEL", case_insensitive))]
+  Apple,
+  #[cstr(name = "pear")]
+  Pear,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(lookup = "binary_search")]
+enum Sorted {
+  #[cstr(alias = "b")]
+  Beta,
+  Alpha,
+  #[cstr(other)]
+  Other(CString),
+}
+
+fn main() {
+  assert_eq!(Constants::from_cstr_detailed(cstr(b"apple\0")), Ok((Constants::Apple, MatchedName::Canonical)));
+  assert_eq!(Constants::from_cstr_detailed(cstr(b"Apple\0")), Ok((Constants::Apple, MatchedName::Ident)));
+  assert_eq!(Constants::from_cstr_detailed(cstr(b"aple\0")), Ok((Constants::Apple, MatchedName::Alias(0))));
+  assert_eq!(Constants::from_cstr_detailed(cstr(b"appel\0")), Ok((Constants::Apple, MatchedName::Alias(1))));
+  assert_eq!(Constants::from_cstr_detailed(cstr(b"Pear\0")), Ok((Constants::Pear, MatchedName::Ident)));
+  assert!(Constants::from_cstr_detailed(cstr(b"plum\0")).is_err());
+  assert_eq!(Constants::from_cstr(cstr(b"aple\0")), Ok(Constants::Apple));
+
+  assert_eq!(Sorted::from_cstr_detailed(cstr(b"b\0")), Ok((Sorted::Beta, MatchedName::Alias(0))));
+  assert_eq!(Sorted::from_cstr_detailed(cstr(b"Alpha\0")), Ok((Sorted::Alpha, MatchedName::Canonical)));
+  assert_eq!(
+    Sorted::from_cstr_detailed(cstr(b"Gamma\0")),
+    Ok((Sorted::Other(CString::new("Gamma").unwrap()), MatchedName::Fallback))
+  );
+}
@@ -0,0 +1,19 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(no_string)]
+enum Enum {
+  #[cstr(name=b"\xFFraw")]
+  Raw,
+  #[cstr(name=b"plain", alias=b"\xFE")]
+  Plain,
+}
+
+fn main() {
+  assert_eq!(Enum::Raw.as_cstr().to_bytes_with_nul(), b"\xFFraw\0");
+  assert_eq!(Enum::Plain.as_cstr().to_bytes_with_nul(), b"plain\0");
+  assert_eq!(Enum::from_cstr(CStr::from_bytes_with_nul(b"\xFFraw\0").unwrap()), Ok(Enum::Raw));
+  assert_eq!(Enum::from_cstr(CStr::from_bytes_with_nul(b"\xFE\0").unwrap()), Ok(Enum::Plain));
+  assert!(Enum::from_cstr(CStr::from_bytes_with_nul(b"raw\0").unwrap()).is_err());
+}
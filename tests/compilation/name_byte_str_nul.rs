@@ -0,0 +1,15 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+enum Trailing {
+  #[cstr(name=b"foo\0")]
+  Foo,
+}
+
+#[derive(AsCStr)]
+enum Interior {
+  #[cstr(name=b"f\0oo")]
+  Foo,
+}
+
+fn main() {}
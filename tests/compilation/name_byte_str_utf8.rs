@@ -0,0 +1,11 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+enum Enum0 {
+  #[cstr(name=b"\xFF")]
+  Apple,
+}
+
+fn main() {
+  let _ = String::from(&Enum0::Apple);
+}
@@ -0,0 +1,44 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+mod names {
+  pub const fn apple() -> &'static [u8] {
+    b"APPLE\0"
+  }
+}
+
+const fn reversed_bacon() -> &'static [u8] {
+  const BYTES: [u8; 6] = {
+    let forward = b"Bacon";
+    let mut out = [0u8; 6];
+    let mut i = 0;
+    while i < forward.len() {
+      out[i] = forward[forward.len() - 1 - i];
+      i += 1;
+    }
+    out
+  };
+  &BYTES
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+enum Enum {
+  #[cstr(name_fn="names::apple")]
+  Apple,
+  #[cstr(name_fn="reversed_bacon")]
+  Bacon,
+  Cat,
+}
+
+fn main() {
+  assert_eq!(Enum::Apple.as_cstr().to_bytes_with_nul(), b"APPLE\0");
+  assert_eq!(Enum::Bacon.as_cstr().to_bytes_with_nul(), b"nocaB\0");
+  assert_eq!(Enum::Cat.as_cstr().to_bytes_with_nul(), b"Cat\0");
+  assert_eq!(Enum::CSTR_VARIANTS[1].to_bytes(), b"nocaB");
+  assert_eq!(Enum::CSTR_NAME_MAX_LEN, 5);
+
+  for v in [Enum::Apple, Enum::Bacon, Enum::Cat] {
+    assert_eq!(Enum::from_cstr(v.as_cstr()), Ok(v));
+  }
+  assert!(Enum::from_cstr(CStr::from_bytes_with_nul(b"Apple\0").unwrap()).is_err());
+}
@@ -0,0 +1,15 @@
+use cstr_enum::*;
+
+const fn no_nul() -> &'static [u8] {
+  b"apple"
+}
+
+#[derive(AsCStr)]
+enum Enum {
+  #[cstr(name_fn="no_nul")]
+  Apple,
+}
+
+fn main() {
+
+}
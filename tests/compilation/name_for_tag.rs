@@ -0,0 +1,52 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(AsCStr)]
+#[repr(u8)]
+enum Contiguous {
+  #[cstr(name="int")]
+  Int = 3,
+  Float = 5,
+  Str = 4,
+}
+
+#[derive(AsCStr)]
+#[repr(u8)]
+enum Gapped {
+  Int = 1,
+  Float = 10,
+  Str,
+}
+
+const BASE: u8 = 7;
+
+#[derive(AsCStr)]
+#[repr(u8)]
+enum Computed {
+  Int = BASE,
+  Float,
+}
+
+fn main() {
+  assert_eq!(Contiguous::name_for_tag(3), Some(cstr(b"int\0")));
+  assert_eq!(Contiguous::name_for_tag(4), Some(cstr(b"Str\0")));
+  assert_eq!(Contiguous::name_for_tag(5), Some(cstr(b"Float\0")));
+  assert_eq!(Contiguous::name_for_tag(0), None);
+  assert_eq!(Contiguous::name_for_tag(2), None);
+  assert_eq!(Contiguous::name_for_tag(6), None);
+  assert_eq!(Contiguous::name_for_tag(u8::MAX), None);
+
+  assert_eq!(Gapped::name_for_tag(1), Some(cstr(b"Int\0")));
+  assert_eq!(Gapped::name_for_tag(10), Some(cstr(b"Float\0")));
+  assert_eq!(Gapped::name_for_tag(11), Some(cstr(b"Str\0")));
+  assert_eq!(Gapped::name_for_tag(0), None);
+  assert_eq!(Gapped::name_for_tag(2), None);
+  assert_eq!(Gapped::name_for_tag(u8::MAX), None);
+
+  assert_eq!(Computed::name_for_tag(7), Some(cstr(b"Int\0")));
+  assert_eq!(Computed::name_for_tag(8), Some(cstr(b"Float\0")));
+  assert_eq!(Computed::name_for_tag(0), None);
+}
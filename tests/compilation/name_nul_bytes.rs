@@ -6,6 +6,13 @@ enum Enum{
   A,
 }
 
+#[derive(FromCStr, AsCStr)]
+enum Enum1 {
+  // only a single terminator is allowed
+  #[cstr(name="apple\0\0")]
+  A,
+}
+
 fn main() {
 
-}
\ No newline at end of file
+}
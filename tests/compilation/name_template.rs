@@ -0,0 +1,39 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(name_template="SLOT_{}")]
+enum Slot {
+  Zero,
+  One,
+  #[cstr(name="SLOT_MAX")]
+  Max,
+  Three = 10,
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(name_template="{}th", accept_ident)]
+enum Ordinal {
+  Zeroth,
+  First,
+}
+
+fn main() {
+  assert_eq!(Slot::Zero.as_cstr().to_bytes_with_nul(), b"SLOT_0\0");
+  assert_eq!(Slot::One.as_cstr().to_bytes_with_nul(), b"SLOT_1\0");
+  assert_eq!(Slot::Max.as_cstr().to_bytes_with_nul(), b"SLOT_MAX\0");
+  assert_eq!(Slot::Three.as_cstr().to_bytes_with_nul(), b"SLOT_3\0");
+
+  for v in [Slot::Zero, Slot::One, Slot::Max, Slot::Three] {
+    assert_eq!(Slot::from_cstr(v.as_cstr()), Ok(v));
+  }
+  assert!(Slot::from_cstr(cstr(b"SLOT_2\0")).is_err());
+  assert!(Slot::from_cstr(cstr(b"Zero\0")).is_err());
+
+  assert_eq!(Ordinal::First.as_cstr().to_bytes(), b"1th");
+  assert_eq!(Ordinal::from_cstr(cstr(b"0th\0")), Ok(Ordinal::Zeroth));
+  assert_eq!(Ordinal::from_cstr(cstr(b"First\0")), Ok(Ordinal::First));
+}
@@ -0,0 +1,21 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[cstr(name_template="SLOT")]
+enum NoPlaceholder {
+  A,
+}
+
+#[derive(AsCStr)]
+#[cstr(name_template="SLOT_{}_{}")]
+enum TwoPlaceholders {
+  A,
+}
+
+#[derive(AsCStr)]
+#[cstr(name_template="SLOT\0{}")]
+enum Nul {
+  A,
+}
+
+fn main() {}
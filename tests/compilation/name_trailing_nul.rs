@@ -0,0 +1,17 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+enum Enum {
+  #[cstr(name="foo\0")]
+  Foo,
+  #[cstr(name="bar", alias="baz\0")]
+  Bar,
+}
+
+fn main() {
+  assert_eq!(Enum::Foo.as_cstr().to_bytes_with_nul(), b"foo\0");
+  assert_eq!(Enum::Bar.as_cstr().to_bytes_with_nul(), b"bar\0");
+  assert_eq!(Enum::from_cstr(CStr::from_bytes_with_nul(b"foo\0").unwrap()), Ok(Enum::Foo));
+  assert_eq!(Enum::from_cstr(CStr::from_bytes_with_nul(b"baz\0").unwrap()), Ok(Enum::Bar));
+}
@@ -0,0 +1,35 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(normalize="ascii_lowercase")]
+enum Enum0 {
+  Apple,
+  #[cstr(name="BaCoN")]
+  Bacon,
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(normalize="ascii_uppercase")]
+enum Enum1 {
+  Apple,
+}
+
+fn main() {
+  assert_eq!(Enum0::Apple.as_cstr().to_bytes_with_nul(), b"apple\0");
+  assert_eq!(Enum0::Bacon.as_cstr().to_bytes_with_nul(), b"bacon\0");
+  assert_eq!(Enum1::Apple.as_cstr().to_bytes_with_nul(), b"APPLE\0");
+
+  for v in [Enum0::Apple, Enum0::Bacon] {
+    assert_eq!(Enum0::from_cstr(v.as_cstr()), Ok(v));
+  }
+  assert_eq!(Enum1::from_cstr(Enum1::Apple.as_cstr()), Ok(Enum1::Apple));
+
+  assert_eq!(Enum0::from_cstr(cstr(b"APPLE\0")), Ok(Enum0::Apple));
+  assert_eq!(Enum0::from_cstr(cstr(b"BaCoN\0")), Ok(Enum0::Bacon));
+  assert_eq!(Enum1::from_cstr(cstr(b"apple\0")), Ok(Enum1::Apple));
+  assert!(Enum0::from_cstr(cstr(b"apples\0")).is_err());
+}
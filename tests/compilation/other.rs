@@ -0,0 +1,55 @@
+use cstr_enum::*;
+use std::ffi::CString;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+enum Enum0 {
+  Apple,
+  #[cstr(other)]
+  Other,
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+enum Enum1 {
+  Apple,
+  #[cstr(other)]
+  Other(CString),
+}
+
+// The catch-all comes first and a name is empty, but both still only get what they should
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(lookup = "binary_search")]
+enum Enum2 {
+  #[cstr(other, name = "unknown")]
+  Other(CString),
+  #[cstr(name = "")]
+  Empty,
+  #[cstr(name = "Other")]
+  Named,
+  #[cstr(alias = "Pear")]
+  Apple,
+}
+
+fn main() {
+  assert_eq!(Enum0::from_cstr(cstr(b"Apple\0")), Ok(Enum0::Apple));
+  assert_eq!(Enum0::from_cstr(cstr(b"Banana\0")), Ok(Enum0::Other));
+
+  assert_eq!(Enum1::from_cstr(cstr(b"Apple\0")), Ok(Enum1::Apple));
+  assert_eq!(Enum1::from_cstr(cstr(b"Banana\0")), Ok(Enum1::Other(CString::new("Banana").unwrap())));
+  assert_eq!(Enum1::from_cstr(cstr(b"Other\0")), Ok(Enum1::Other(CString::new("Other").unwrap())));
+  assert_eq!(
+    Enum1::from_byte_iter(b"much too long\0ignored".iter().copied()),
+    Ok(Enum1::Other(CString::new("much too long").unwrap()))
+  );
+  assert_eq!(Enum1::Other(CString::new("Banana").unwrap()).as_cstr(), cstr(b"Other\0"));
+
+  assert_eq!(Enum2::from_cstr(cstr(b"\0")), Ok(Enum2::Empty));
+  assert_eq!(Enum2::from_cstr(cstr(b"Other\0")), Ok(Enum2::Named));
+  assert_eq!(Enum2::from_cstr(cstr(b"Pear\0")), Ok(Enum2::Apple));
+  assert_eq!(Enum2::from_cstr(cstr(b"Pears\0")), Ok(Enum2::Other(CString::new("Pears").unwrap())));
+  assert_eq!(Enum2::from_cstr(cstr(b"Appl\0")), Ok(Enum2::Other(CString::new("Appl").unwrap())));
+  assert_eq!(Enum2::from_byte_iter(b"Other".iter().copied()), Ok(Enum2::Named));
+}
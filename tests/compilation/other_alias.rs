@@ -0,0 +1,11 @@
+use cstr_enum::*;
+use std::ffi::CString;
+
+#[derive(FromCStr)]
+enum Enum {
+  Apple,
+  #[cstr(other, alias = "unknown")]
+  Other(CString),
+}
+
+fn main() {}
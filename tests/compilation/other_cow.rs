@@ -0,0 +1,43 @@
+use cstr_enum::*;
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+enum Enum {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon,
+  #[cstr(other, transparent)]
+  Other(Cow<'static, CStr>),
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Qualified {
+  Apple,
+  #[cstr(other)]
+  Other(std::borrow::Cow<'static, std::ffi::CStr>),
+}
+
+fn main() {
+  assert_eq!(Enum::from_cstr(cstr(b"Apple\0")), Ok(Enum::Apple));
+  assert_eq!(Enum::from_cstr(cstr(b"pork\0")), Ok(Enum::Bacon));
+  match Enum::from_cstr(cstr(b"Banana\0")) {
+    Ok(Enum::Other(Cow::Owned(s))) => assert_eq!(s, CString::new("Banana").unwrap()),
+    other => panic!("expected an owned name, got {:?}", other),
+  }
+  assert_eq!(
+    Enum::from_byte_iter(b"much too long\0ignored".iter().copied()),
+    Ok(Enum::Other(Cow::Owned(CString::new("much too long").unwrap())))
+  );
+
+  // Known names can also be held without allocating
+  let borrowed = Enum::Other(Cow::Borrowed(Enum::Apple.as_cstr()));
+  assert_eq!(borrowed.as_cstr(), Enum::Apple.as_cstr());
+  assert_eq!(Enum::from_cstr(borrowed.as_cstr()), Ok(Enum::Apple));
+
+  assert_eq!(Qualified::from_cstr(cstr(b"x\0")), Ok(Qualified::Other(Cow::Owned(CString::new("x").unwrap()))));
+}
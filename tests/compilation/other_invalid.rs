@@ -0,0 +1,33 @@
+use cstr_enum::*;
+use std::ffi::CString;
+
+#[derive(FromCStr)]
+enum Enum0 {
+  #[cstr(other)]
+  Unknown,
+  #[cstr(other)]
+  Other,
+}
+
+#[derive(FromCStr)]
+enum Enum1 {
+  Apple,
+  #[cstr(other)]
+  Other(CString, u32),
+}
+
+#[derive(FromCStr)]
+enum Enum2 {
+  Apple,
+  #[cstr(other)]
+  Other(String),
+}
+
+#[derive(FromCStr)]
+enum Enum3 {
+  Apple,
+  #[cstr(other)]
+  Other { name: CString },
+}
+
+fn main() {}
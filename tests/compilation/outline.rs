@@ -0,0 +1,28 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+#[derive(AsCStr)]
+#[cstr(outline)]
+enum Enum0 {
+  Apple,
+  #[cstr(name = "pork")]
+  Bacon { grams: u32 },
+}
+
+#[derive(AsCStr)]
+#[cstr(outline)]
+enum Enum1<'a, T> {
+  Red(T),
+  #[cstr(transparent)]
+  Other(&'a CStr),
+}
+
+fn main() {
+  assert_eq!(Enum0::Apple.as_cstr().to_bytes(), b"Apple");
+  assert_eq!(Enum0::Bacon { grams: 2 }.as_cstr().to_bytes(), b"pork");
+  assert_eq!(Enum0::CSTR_VARIANTS, [Enum0::Apple.as_cstr(), Enum0::Bacon { grams: 0 }.as_cstr()]);
+
+  let mauve = CStr::from_bytes_with_nul(b"mauve\0").unwrap();
+  assert_eq!(Enum1::<u8>::Other(mauve).as_cstr(), mauve);
+  assert_eq!(Enum1::Red(1u8).as_cstr().to_bytes(), b"Red");
+}
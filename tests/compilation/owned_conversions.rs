@@ -0,0 +1,25 @@
+use cstr_enum::*;
+use std::ffi::CString;
+
+const fn bytes_name() -> &'static [u8] {
+  b"from_fn\0"
+}
+
+#[derive(AsCStr)]
+enum Enum0 {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon,
+  #[cstr(name_fn="bytes_name")]
+  Cat,
+  Dog(#[allow(dead_code)] u8),
+}
+
+fn main() {
+  let all = [Enum0::Apple, Enum0::Bacon, Enum0::Cat, Enum0::Dog(0)];
+  let names: Vec<String> = all.iter().map(String::from).collect();
+  assert_eq!(names, ["Apple", "pork", "from_fn", "Dog"]);
+  let cnames: Vec<CString> = all.iter().map(CString::from).collect();
+  assert_eq!(cnames[1].as_bytes_with_nul(), b"pork\0");
+  assert_eq!(CString::from(&Enum0::Cat).as_bytes(), b"from_fn");
+}
@@ -0,0 +1,15 @@
+use cstr_enum::*;
+
+const fn not_utf8() -> &'static [u8] {
+  b"\xff\0"
+}
+
+#[derive(AsCStr)]
+enum Enum0 {
+  #[cstr(name_fn="not_utf8")]
+  Apple,
+}
+
+fn main() {
+  let _ = String::from(&Enum0::Apple);
+}
@@ -0,0 +1,32 @@
+use cstr_enum::*;
+use std::ffi::CString;
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Enum {
+  Apple,
+  Bacon,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Capture {
+  Apple,
+  #[cstr(other)]
+  Other(CString),
+}
+
+fn main() {
+  assert_eq!(Enum::from_pascal_bytes(b"\x05Apple"), Ok(Enum::Apple));
+  // trailing bytes after the name are ignored, as is nul padding
+  assert_eq!(Enum::from_pascal_bytes(b"\x05Bacon, and more"), Ok(Enum::Bacon));
+  assert_eq!(Enum::from_pascal_bytes(b"\x08Bacon\0\0\0"), Ok(Enum::Bacon));
+  assert!(Enum::from_pascal_bytes(b"\x04Apple").is_err());
+
+  let unknown = Enum::from_pascal_bytes(b"\x03Cat").unwrap_err();
+  let out_of_range = Enum::from_pascal_bytes(b"\x06Apple").unwrap_err();
+  assert_ne!(unknown, out_of_range);
+  assert_eq!(Enum::from_pascal_bytes(b"\xffApple").unwrap_err(), out_of_range);
+  assert_eq!(Enum::from_pascal_bytes(b"").unwrap_err(), out_of_range);
+  assert_ne!(Enum::from_pascal_bytes(b"\0").unwrap_err(), out_of_range);
+
+  assert_eq!(Capture::from_pascal_bytes(b"\x03Cat"), Ok(Capture::Other(CString::new("Cat").unwrap())));
+}
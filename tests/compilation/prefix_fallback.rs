@@ -0,0 +1,48 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(prefix_fallback)]
+enum Enum0 {
+  Apple,
+  Apricot,
+  Banana,
+  Ban,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(prefix_fallback, normalize="ascii_lowercase")]
+enum Enum1 {
+  Apple,
+  Apricot,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Enum2 {
+  Apple,
+}
+
+fn main() {
+  // unique prefixes
+  assert_eq!(Enum0::from_cstr(cstr(b"Appl\0")), Ok(Enum0::Apple));
+  assert_eq!(Enum0::from_cstr(cstr(b"Apr\0")), Ok(Enum0::Apricot));
+  assert_eq!(Enum0::from_cstr(cstr(b"Bana\0")), Ok(Enum0::Banana));
+  assert_eq!(Enum1::from_cstr(cstr(b"APR\0")), Ok(Enum1::Apricot));
+
+  // ambiguous prefixes
+  assert!(Enum0::from_cstr(cstr(b"Ap\0")).is_err());
+  assert!(Enum0::from_cstr(cstr(b"\0")).is_err());
+  assert!(Enum1::from_cstr(cstr(b"ap\0")).is_err());
+
+  // an exact match takes precedence over being a prefix of another name
+  assert_eq!(Enum0::from_cstr(cstr(b"Ban\0")), Ok(Enum0::Ban));
+
+  // not a prefix of anything
+  assert!(Enum0::from_cstr(cstr(b"Apples\0")).is_err());
+
+  // off by default
+  assert!(Enum2::from_cstr(cstr(b"App\0")).is_err());
+}
@@ -0,0 +1,48 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, AsCStr, FromCStr)]
+#[cstr(prefix = "GL_")]
+enum Enum0 {
+  Triangles,
+  #[cstr(name = "lines", alias = "segments")]
+  Lines,
+  #[cstr(verbatim, name = "NONE")]
+  Empty,
+  #[cstr(accept_ident)]
+  Points,
+}
+
+#[derive(Debug, PartialEq, AsCStr, FromCStr)]
+#[cstr(suffix = "_t", prefix = "my_", rename_all = "snake_case", case_insensitive)]
+enum Enum1 {
+  SizeType,
+  #[cstr(verbatim)]
+  Raw,
+}
+
+#[derive(AsCStr)]
+#[cstr(suffix = "!", name_template = "v{}", normalize = "ascii_lowercase")]
+enum Enum2 {
+  Zero,
+  One,
+}
+
+fn main() {
+  assert_eq!(Enum0::CSTR_VARIANTS, [cstr(b"GL_Triangles\0"), cstr(b"GL_lines\0"), cstr(b"NONE\0"), cstr(b"GL_Points\0")]);
+  assert_eq!(Enum0::from_cstr(cstr(b"GL_segments\0")), Ok(Enum0::Lines));
+  assert_eq!(Enum0::from_cstr(cstr(b"NONE\0")), Ok(Enum0::Empty));
+  // Only the name gets the prefix, not the identifier
+  assert_eq!(Enum0::from_cstr(cstr(b"Points\0")), Ok(Enum0::Points));
+  assert!(Enum0::from_cstr(cstr(b"Triangles\0")).is_err());
+  assert!(Enum0::from_cstr(cstr(b"GL_NONE\0")).is_err());
+
+  assert_eq!(Enum1::SizeType.as_cstr().to_bytes(), b"my_size_type_t");
+  assert_eq!(Enum1::Raw.as_cstr().to_bytes(), b"raw");
+  assert_eq!(Enum1::from_cstr(cstr(b"MY_SIZE_TYPE_T\0")), Ok(Enum1::SizeType));
+
+  assert_eq!(Enum2::CSTR_VARIANTS, [cstr(b"v0!\0"), cstr(b"v1!\0")]);
+}
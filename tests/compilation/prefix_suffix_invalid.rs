@@ -0,0 +1,30 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[cstr(prefix = "GL\0")]
+enum Enum0 {
+  Triangles,
+}
+
+#[derive(AsCStr)]
+#[cstr(suffix = 1)]
+enum Enum1 {
+  Triangles,
+}
+
+#[derive(AsCStr)]
+#[cstr(prefix = "a", prefix = "b")]
+enum Enum2 {
+  Triangles,
+}
+
+// The prefix can make names collide
+#[derive(AsCStr)]
+#[cstr(prefix = "GL_")]
+enum Enum3 {
+  Triangles,
+  #[cstr(name = "GL_Triangles", verbatim)]
+  Tris,
+}
+
+fn main() {}
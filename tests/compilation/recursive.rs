@@ -0,0 +1,18 @@
+use cstr_enum::*;
+
+#[derive(AsCStr, CStrDebugEscaped)]
+enum Tree {
+  Leaf,
+  Node(Box<Tree>, Box<Tree>),
+  #[cstr(name="forest")]
+  Forest { trees: Vec<Self> },
+}
+
+fn main() {
+  let tree = Tree::Node(Box::new(Tree::Leaf), Box::new(Tree::Node(Box::new(Tree::Leaf), Box::new(Tree::Leaf))));
+  assert_eq!(Tree::Leaf.as_cstr().to_bytes(), b"Leaf");
+  assert_eq!(tree.as_cstr().to_bytes(), b"Node");
+  assert_eq!(Tree::Forest { trees: vec![tree] }.as_cstr().to_bytes(), b"forest");
+  assert_eq!(Tree::CSTR_VARIANTS.len(), 3);
+  assert_eq!(format!("{:?}", Tree::Leaf), "Leaf");
+}
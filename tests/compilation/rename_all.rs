@@ -0,0 +1,82 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(acronyms("HTTP", "URL"), rename_all="SCREAMING_SNAKE_CASE")]
+enum Screaming {
+  HttpUrl,
+  HttpServer,
+  HTTPURLParser,
+  Httpd,
+  #[cstr(name="Custom")]
+  Renamed,
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(rename_all="snake_case", acronyms("HTTP"))]
+#[allow(non_camel_case_types)]
+enum Snake {
+  HttpServer,
+  XMLHttpRequest,
+  Version2Beta,
+  Already_Split,
+  r#Type,
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(rename_all="kebab-case")]
+enum Kebab {
+  HttpServerError,
+  #[cstr(name="Custom")]
+  Renamed,
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(rename_all="camelCase", acronyms("URL"))]
+enum Camel {
+  HttpServerError,
+  HTTPServer,
+  ParseURL,
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(rename_all="lowercase")]
+#[allow(non_camel_case_types)]
+enum Lower {
+  HttpServerError,
+  Already_Split,
+}
+
+fn main() {
+  assert_eq!(Screaming::HttpUrl.as_cstr(), cstr(b"HTTP_URL\0"));
+  assert_eq!(Screaming::HttpServer.as_cstr(), cstr(b"HTTP_SERVER\0"));
+  assert_eq!(Screaming::HTTPURLParser.as_cstr(), cstr(b"HTTP_URL_PARSER\0"));
+  // not followed by another word, so not the acronym
+  assert_eq!(Screaming::Httpd.as_cstr(), cstr(b"HTTPD\0"));
+  assert_eq!(Screaming::Renamed.as_cstr(), cstr(b"Custom\0"));
+  assert_eq!(Screaming::from_cstr(cstr(b"HTTP_URL\0")), Ok(Screaming::HttpUrl));
+  assert!(Screaming::from_cstr(cstr(b"HttpUrl\0")).is_err());
+
+  // acronyms stay uppercase
+  assert_eq!(Snake::HttpServer.as_cstr(), cstr(b"HTTP_server\0"));
+  assert_eq!(Snake::XMLHttpRequest.as_cstr(), cstr(b"xml_HTTP_request\0"));
+  assert_eq!(Snake::Version2Beta.as_cstr(), cstr(b"version2_beta\0"));
+  assert_eq!(Snake::Already_Split.as_cstr(), cstr(b"already_split\0"));
+  assert_eq!(Snake::r#Type.as_cstr(), cstr(b"type\0"));
+  assert_eq!(Snake::from_cstr(cstr(b"HTTP_server\0")), Ok(Snake::HttpServer));
+
+  assert_eq!(Kebab::HttpServerError.as_cstr(), cstr(b"http-server-error\0"));
+  assert_eq!(Kebab::Renamed.as_cstr(), cstr(b"Custom\0"));
+  assert_eq!(Kebab::from_cstr(cstr(b"http-server-error\0")), Ok(Kebab::HttpServerError));
+
+  assert_eq!(Camel::HttpServerError.as_cstr(), cstr(b"httpServerError\0"));
+  assert_eq!(Camel::HTTPServer.as_cstr(), cstr(b"httpServer\0"));
+  assert_eq!(Camel::ParseURL.as_cstr(), cstr(b"parseURL\0"));
+  assert_eq!(Camel::from_cstr(cstr(b"httpServerError\0")), Ok(Camel::HttpServerError));
+
+  assert_eq!(Lower::HttpServerError.as_cstr(), cstr(b"httpservererror\0"));
+  assert_eq!(Lower::Already_Split.as_cstr(), cstr(b"alreadysplit\0"));
+}
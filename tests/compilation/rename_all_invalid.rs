@@ -0,0 +1,27 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[cstr(rename_all="Train-Case")]
+enum Enum0 {
+  Apple,
+}
+
+#[derive(AsCStr)]
+#[cstr(acronyms("HTTP"))]
+enum Enum1 {
+  HttpServer,
+}
+
+#[derive(AsCStr)]
+#[cstr(rename_all="snake_case", acronyms("HTTP", "U-R-L"))]
+enum Enum2 {
+  HttpServer,
+}
+
+#[derive(AsCStr)]
+#[cstr(rename_all="snake_case", name_template="SLOT_{}")]
+enum Enum3 {
+  Apple,
+}
+
+fn main() {}
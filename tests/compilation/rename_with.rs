@@ -0,0 +1,67 @@
+use cstr_enum::*;
+use std::cell::Cell;
+use std::ffi::CString;
+
+thread_local! {
+  static CALLS: Cell<usize> = Cell::new(0);
+}
+
+fn snake_case(ident: &str) -> String {
+  CALLS.with(|c| c.set(c.get() + 1));
+  let mut name = String::new();
+  for (i, c) in ident.chars().enumerate() {
+    if i > 0 && c.is_uppercase() {
+      name.push('_');
+    }
+    name.push(c.to_ascii_lowercase());
+  }
+  name
+}
+
+mod casing {
+  pub fn reversed(ident: &str) -> &'static str {
+    match ident {
+      "Apple" => "elppA",
+      _ => "?",
+    }
+  }
+}
+
+#[derive(AsCStr, CStrDispatch)]
+#[cstr(rename_with="snake_case")]
+enum Enum {
+  MaxRetries,
+  #[cstr(name="timeout")]
+  TimeoutMillis,
+  Plain,
+}
+
+#[derive(AsCStr)]
+#[cstr(rename_with="casing::reversed")]
+enum Static {
+  Apple,
+  Bacon { crispy: bool },
+}
+
+fn main() {
+  assert_eq!(Enum::MaxRetries.as_cstr().to_bytes_with_nul(), b"max_retries\0");
+  assert_eq!(Enum::TimeoutMillis.as_cstr().to_bytes_with_nul(), b"timeout\0");
+  assert_eq!(Enum::Plain.as_cstr().to_bytes_with_nul(), b"plain\0");
+  // Each name is only computed once
+  let calls = CALLS.with(Cell::get);
+  assert_eq!(calls, 2);
+  assert!(std::ptr::eq(Enum::MaxRetries.as_cstr(), Enum::MaxRetries.as_cstr()));
+  assert_eq!(CALLS.with(Cell::get), calls);
+
+  let names: Vec<String> = [Enum::MaxRetries, Enum::Plain].iter().map(String::from).collect();
+  assert_eq!(names, ["max_retries", "plain"]);
+  assert_eq!(CString::from(&Enum::Plain).as_bytes(), b"plain");
+  assert_eq!(Enum::cstr_for_str("max_retries").unwrap().to_bytes(), b"max_retries");
+  assert_eq!(Enum::cstr_for_str("MaxRetries"), None);
+
+  let handlers = EnumHandlers { max_retries: || 1, timeout_millis: || 2, plain: || 3 };
+  assert_eq!(Enum::dispatch(Enum::Plain.as_cstr(), &handlers), Some(3));
+
+  assert_eq!(Static::Apple.as_cstr().to_bytes(), b"elppA");
+  assert_eq!(Static::Bacon { crispy: true }.as_cstr().to_bytes(), b"?");
+}
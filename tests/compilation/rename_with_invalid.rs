@@ -0,0 +1,33 @@
+use cstr_enum::*;
+
+fn upper(ident: &str) -> String {
+  ident.to_uppercase()
+}
+
+#[derive(FromCStr)]
+#[cstr(rename_with="upper")]
+enum Parsed {
+  Apple,
+}
+
+#[derive(AsCStr)]
+#[cstr(rename_with="upper", total_bytes_max = 10)]
+enum Budget {
+  Apple,
+}
+
+#[derive(AsCStr)]
+#[cstr(rename_with="upper", normalize="ascii_lowercase")]
+enum Normalized {
+  Apple,
+}
+
+#[derive(AsCStr)]
+#[cstr(rename_with="upper")]
+enum Runtime {
+  Apple,
+}
+
+fn main() {
+  let _ = Runtime::CSTR_VARIANTS;
+}
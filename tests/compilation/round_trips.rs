@@ -0,0 +1,29 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+const fn short() -> &'static [u8] {
+  b"short\0"
+}
+
+const LONG: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"long\0") };
+
+#[derive(Debug, Clone, Copy, PartialEq, FromCStr, AsCStr)]
+#[cstr(emit_discriminant_conversions)]
+#[repr(u8)]
+enum Width {
+  #[cstr(name_fn="short")]
+  Short = 2,
+  #[cstr(cstr="LONG")]
+  Long = 8,
+  #[cstr(name="Int")]
+  Float = 4,
+  #[cstr(name="Float")]
+  Int = 0,
+}
+
+fn main() {
+  for &v in &[Width::Short, Width::Long, Width::Float, Width::Int] {
+    let v = Width::try_from_i64(v as i64).unwrap();
+    assert_eq!(Width::from_cstr(v.as_cstr()), Ok(v));
+  }
+}
@@ -0,0 +1,15 @@
+use cstr_enum::*;
+
+const fn short() -> &'static [u8] {
+  b"Long\0"
+}
+
+#[derive(AsCStr)]
+#[cstr(emit_discriminant_conversions)]
+enum Width {
+  #[cstr(name_fn="short")]
+  Short,
+  Long,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+#[cstr(separator_insensitive)]
+enum Enum0 {
+  #[cstr(name="max-retries")]
+  MaxRetries,
+  #[cstr(name="max_retries")]
+  MaxRetriesOld,
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(separator_insensitive)]
+enum Enum0 {
+  #[cstr(name="max-retries")]
+  MaxRetries,
+  #[cstr(name="time_out")]
+  TimeOut,
+  Plain,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(separator_insensitive, normalize="ascii_lowercase", prefix_fallback)]
+enum Enum1 {
+  #[cstr(name="max-retries")]
+  MaxRetries,
+}
+
+fn main() {
+  assert_eq!(Enum0::from_cstr(cstr(b"max-retries\0")), Ok(Enum0::MaxRetries));
+  assert_eq!(Enum0::from_cstr(cstr(b"max_retries\0")), Ok(Enum0::MaxRetries));
+  assert_eq!(Enum0::from_cstr(cstr(b"time-out\0")), Ok(Enum0::TimeOut));
+  assert_eq!(Enum0::from_cstr(cstr(b"time_out\0")), Ok(Enum0::TimeOut));
+  assert_eq!(Enum0::from_cstr(cstr(b"Plain\0")), Ok(Enum0::Plain));
+  assert!(Enum0::from_cstr(cstr(b"max retries\0")).is_err());
+  assert!(Enum0::from_cstr(cstr(b"MAX_RETRIES\0")).is_err());
+  assert_eq!(Enum0::MaxRetries.as_cstr(), cstr(b"max-retries\0"));
+
+  assert_eq!(Enum1::from_cstr(cstr(b"MAX_RETRIES\0")), Ok(Enum1::MaxRetries));
+  assert_eq!(Enum1::from_cstr(cstr(b"Max_Re\0")), Ok(Enum1::MaxRetries));
+}
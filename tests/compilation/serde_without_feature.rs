@@ -0,0 +1,9 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[cstr(serde)]
+enum Enum0 {
+  Apple,
+}
+
+fn main() {}
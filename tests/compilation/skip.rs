@@ -0,0 +1,32 @@
+use cstr_enum::*;
+use std::ffi::CString;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, FromCWStr, AsCStr)]
+enum Enum0 {
+  Apple,
+  #[cstr(skip, name = "internal")]
+  Internal,
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+enum Enum1 {
+  Apple,
+  #[cstr(skip)]
+  Internal,
+  #[cstr(other)]
+  Other(CString),
+}
+
+fn main() {
+  assert_eq!(Enum0::Internal.as_cstr(), cstr(b"internal\0"));
+  assert_eq!(Enum0::from_cstr(cstr(b"Apple\0")), Ok(Enum0::Apple));
+  assert!(Enum0::from_cstr(cstr(b"internal\0")).is_err());
+  assert!(Enum0::from_cwstr(&"internal\0".encode_utf16().collect::<Vec<_>>()).is_err());
+
+  assert_eq!(Enum1::Internal.as_cstr(), cstr(b"Internal\0"));
+  assert_eq!(Enum1::from_cstr(cstr(b"Internal\0")), Ok(Enum1::Other(CString::new("Internal").unwrap())));
+}
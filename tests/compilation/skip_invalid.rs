@@ -0,0 +1,15 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+enum Enum0 {
+  #[cstr(skip, alias = "apple")]
+  Apple,
+}
+
+#[derive(FromCStr)]
+enum Enum1 {
+  #[cstr(skip, default)]
+  Apple,
+}
+
+fn main() {}
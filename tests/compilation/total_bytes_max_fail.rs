@@ -0,0 +1,10 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[cstr(total_bytes_max = 11)]
+enum Enum0 {
+  Apple,
+  Bacon,
+}
+
+fn main() {}
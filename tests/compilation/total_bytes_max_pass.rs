@@ -0,0 +1,25 @@
+use cstr_enum::*;
+
+const fn computed() -> &'static [u8] {
+  b"computed\0"
+}
+
+#[derive(AsCStr)]
+#[cstr(total_bytes_max = 20)]
+#[allow(dead_code)]
+enum Enum0 {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon,
+  #[cstr(name_fn="computed")]
+  Cat,
+}
+
+#[derive(AsCStr)]
+#[cstr(total_bytes_max = 256)]
+#[allow(dead_code)]
+enum Enum1 {
+  Apple,
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+use cstr_enum::*;
+use std::ffi::{CStr, CString};
+
+#[derive(AsCStr)]
+enum Enum0<'a> {
+  #[cstr(transparent)]
+  Borrowed(&'a CStr),
+  Named,
+  #[cstr(name="pork")]
+  Bacon,
+}
+
+#[derive(AsCStr)]
+enum Enum1 {
+  #[cstr(transparent)]
+  Owned(CString),
+  Named,
+}
+
+#[derive(AsCStr)]
+#[allow(dead_code)]
+enum Enum2<'a> {
+  Wrapped(&'a str),
+}
+
+fn name<T: AsCStr>(value: &T) -> CString {
+  value.as_cstr().to_owned()
+}
+
+fn main() {
+  let held = CString::new("held").unwrap();
+  let borrowed = Enum0::Borrowed(&held);
+  assert_eq!(borrowed.as_cstr(), held.as_c_str());
+  assert_eq!(borrowed.as_cstr().as_ptr(), held.as_ptr());
+  assert_eq!(Enum0::Named.as_cstr().to_bytes(), b"Named");
+  assert_eq!(name(&Enum0::Bacon).as_bytes(), b"pork");
+  assert_eq!(Enum0::CSTR_VARIANTS.len(), 2);
+  assert_eq!(CString::from(&borrowed), held);
+
+  assert_eq!(Enum1::Owned(CString::new("owned").unwrap()).as_cstr().to_bytes(), b"owned");
+  assert_eq!(Enum1::Named.as_cstr().to_bytes(), b"Named");
+
+  assert_eq!(Enum2::Wrapped("ignored").as_cstr().to_bytes(), b"Wrapped");
+  assert_eq!(String::from(&Enum2::Wrapped("ignored")), "Wrapped");
+}
@@ -0,0 +1,52 @@
+use cstr_enum::*;
+use std::ffi::CString;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(trim)]
+enum Enum0 {
+  Apple,
+  #[cstr(name = "red apple")]
+  RedApple,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Enum1 {
+  Apple,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(trim)]
+enum Enum2 {
+  Apple,
+  #[cstr(other)]
+  Other(CString),
+}
+
+fn main() {
+  assert_eq!(Enum0::from_cstr(cstr(b"  Apple  \0")), Ok(Enum0::Apple));
+  assert_eq!(Enum0::from_cstr(cstr(b"\tred apple\n\0")), Ok(Enum0::RedApple));
+  assert!(Enum0::from_cstr(cstr(b"red  apple\0")).is_err());
+  // Only ASCII whitespace is trimmed
+  assert!(Enum0::from_cstr(cstr("Apple\u{a0}\0".as_bytes())).is_err());
+  assert!(Enum1::from_cstr(cstr(b"  Apple  \0")).is_err());
+
+  assert_eq!(Enum0::from_cstr_bytes(b" Apple"), Ok(Enum0::Apple));
+  assert_eq!(Enum0::from_cstr_detailed(cstr(b"Apple \0")), Ok((Enum0::Apple, MatchedName::Canonical)));
+  assert_eq!(Enum0::cstr_for_str(" red apple "), Some(Enum0::RedApple.as_cstr()));
+
+  // Padding longer than any name doesn't fill the buffer
+  assert_eq!(Enum0::from_byte_iter(b"            Apple            ".iter().copied()), Ok(Enum0::Apple));
+  assert_eq!(Enum0::from_byte_iter(b"  red apple            ".iter().copied()), Ok(Enum0::RedApple));
+  assert!(Enum0::from_byte_iter(b"red apple    x".iter().copied()).is_err());
+  assert_eq!(Enum0::from_cstr_field(b"xx Apple   \0\0", 2, 11), Ok(Enum0::Apple));
+
+  assert_eq!(Enum2::from_cstr(cstr(b" Apple \0")), Ok(Enum2::Apple));
+  assert_eq!(Enum2::from_cstr(cstr(b" Pear \0")), Ok(Enum2::Other(CString::new("Pear").unwrap())));
+  // Whitespace after a name of the longest length is only dropped at the end of the input
+  assert_eq!(Enum2::from_byte_iter(b"Apple   ".iter().copied()), Ok(Enum2::Apple));
+  assert_eq!(Enum2::from_byte_iter(b" Apple  x ".iter().copied()), Ok(Enum2::Other(CString::new("Apple  x").unwrap())));
+}
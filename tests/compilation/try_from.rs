@@ -0,0 +1,46 @@
+use cstr_enum::*;
+use std::convert::{TryFrom, TryInto};
+use std::ffi::CStr;
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Plain {
+  Apple,
+  #[cstr(name="pear")]
+  Pear,
+}
+
+#[derive(Debug, PartialEq)]
+struct BadName;
+
+impl From<&'static str> for BadName {
+  fn from(_: &'static str) -> Self {
+    BadName
+  }
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(error = "BadName")]
+enum Custom {
+  Apple,
+}
+
+#[derive(Debug, PartialEq, FromCStr)]
+enum Generic<const N: usize> {
+  Apple,
+}
+
+fn parse<'a, T: TryFrom<&'a CStr>>(s: &'a CStr) -> Result<T, T::Error> {
+  T::try_from(s)
+}
+
+fn main() {
+  let pear = CStr::from_bytes_with_nul(b"pear\0").unwrap();
+  let unknown = CStr::from_bytes_with_nul(b"unknown\0").unwrap();
+  assert_eq!(Plain::try_from(pear), Ok(Plain::Pear));
+  assert_eq!(parse::<Plain>(pear), Ok(Plain::Pear));
+  assert!(Plain::try_from(unknown).is_err());
+  let apple: Result<Custom, BadName> = CStr::from_bytes_with_nul(b"Apple\0").unwrap().try_into();
+  assert_eq!(apple, Ok(Custom::Apple));
+  assert_eq!(Custom::try_from(unknown), Err(BadName));
+  assert_eq!(parse::<Generic<3>>(CStr::from_bytes_with_nul(b"Apple\0").unwrap()), Ok(Generic::Apple));
+}
@@ -0,0 +1,20 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+enum Inner {
+  Apple,
+  #[cstr(name="pork")]
+  Bacon,
+}
+
+type Alias = Inner;
+
+fn main() {
+  let s = CStr::from_bytes_with_nul(b"pork\0").unwrap();
+  assert_eq!(Alias::from_cstr(s), Ok(Alias::Bacon));
+  assert_eq!(Alias::Apple.as_cstr().to_bytes(), b"Apple");
+  assert_eq!(<Alias as FromCStr>::from_cstr(Alias::Apple.as_cstr()), Ok(Inner::Apple));
+  assert_eq!(Alias::CSTR_VARIANTS, Inner::CSTR_VARIANTS);
+  assert_eq!(Alias::from_byte_iter(b"Apple".to_vec()), Ok(Alias::Apple));
+}
@@ -0,0 +1,19 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(unit_error)]
+enum Enum0 {
+  Apple,
+}
+
+fn main() {
+  let ok: Result<Enum0, ()> = Enum0::from_cstr(cstr(b"Apple\0"));
+  assert_eq!(ok, Ok(Enum0::Apple));
+  assert_eq!(Enum0::from_cstr(cstr(b"Banana\0")), Err(()));
+  assert_eq!(Enum0::from_byte_iter(b"much too long".iter().copied()), Err(()));
+  let _: fn(()) = |_: <Enum0 as FromCStr>::Err| {};
+}
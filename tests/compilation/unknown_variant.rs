@@ -0,0 +1,40 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(unknown_variant_error)]
+enum Constants {
+  Apple,
+  Pear,
+}
+
+fn main() {
+  assert_eq!(Constants::from_cstr(cstr(b"Apple\0")), Ok(Constants::Apple));
+
+  let err = Constants::from_cstr(cstr(b"Banana\0")).unwrap_err();
+  assert_eq!(err.got.as_bytes(), b"Banana");
+  assert_eq!(err.enum_name, "Constants");
+  assert_eq!(err.to_string(), "unexpected string \"Banana\" while parsing for Constants variant");
+
+  let err = Constants::from_cstr(cstr(b"\0")).unwrap_err();
+  assert_eq!(err.got.as_bytes(), b"");
+
+  // Longer than any name, and it isn't cut short
+  let err = Constants::from_byte_iter(b"Bananas\0rest".iter().copied()).unwrap_err();
+  assert_eq!(err.got.as_bytes(), b"Bananas");
+  let err = Constants::from_byte_iter(b"Pea".iter().copied()).unwrap_err();
+  assert_eq!(err.got.as_bytes(), b"Pea");
+
+  let err = Constants::from_cstr_field(b"..Plum\0..", 2, 6).unwrap_err();
+  assert_eq!(err.got.as_bytes(), b"Plum");
+  let err = Constants::from_cstr_field(b"..Plum", 2, 10).unwrap_err();
+  assert_eq!(err.got.as_bytes(), b"Plum");
+  let err = Constants::from_pascal_bytes(b"").unwrap_err();
+  assert_eq!(err.got.as_bytes(), b"");
+
+  let err: std::io::Error = err.into();
+  assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,21 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+#[cstr(unknown_variant_error, unit_error)]
+enum Enum0 {
+  Apple,
+}
+
+#[derive(FromCStr)]
+#[cstr(error = "String", unknown_variant_error)]
+enum Enum1 {
+  Apple,
+}
+
+#[derive(FromCWStr)]
+#[cstr(unknown_variant_error)]
+enum Enum2 {
+  Apple,
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+// Only warns without `deny_unparseable`
+#[derive(Debug, FromCStr)]
+enum Empty {}
+
+#[derive(Debug, PartialEq, FromCStr)]
+#[cstr(deny_unparseable)]
+enum OnlyDefault {
+  #[cstr(default)]
+  Unknown,
+}
+
+fn main() {
+  let s = CStr::from_bytes_with_nul(b"Apple\0").unwrap();
+  assert!(Empty::from_cstr(s).is_err());
+  assert_eq!(OnlyDefault::from_cstr(s), Ok(OnlyDefault::Unknown));
+}
@@ -0,0 +1,7 @@
+use cstr_enum::*;
+
+#[derive(FromCStr)]
+#[cstr(deny_unparseable)]
+enum Empty {}
+
+fn main() {}
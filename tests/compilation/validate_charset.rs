@@ -0,0 +1,22 @@
+use cstr_enum::*;
+
+#[derive(AsCStr, FromCStr)]
+#[cstr(validate_charset="ascii_alnum_underscore")]
+enum Constants {
+  #[cstr(name="MAX_VALUE", alias="max_value")]
+  MaxValue,
+  Min2,
+}
+
+#[derive(AsCStr)]
+#[cstr(validate_charset="ascii_printable")]
+enum Messages {
+  #[cstr(name="hello, world!")]
+  Hello,
+}
+
+fn main() {
+  assert_eq!(Constants::MaxValue.as_cstr().to_bytes(), b"MAX_VALUE");
+  assert_eq!(Constants::Min2.as_cstr().to_bytes(), b"Min2");
+  assert_eq!(Messages::Hello.as_cstr().to_bytes(), b"hello, world!");
+}
@@ -0,0 +1,30 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[cstr(validate_charset="ascii_alnum_underscore")]
+enum Space {
+  #[cstr(name="max value")]
+  MaxValue,
+}
+
+#[derive(FromCStr)]
+#[cstr(validate_charset="ascii_alnum")]
+enum Alias {
+  #[cstr(alias="max-value")]
+  MaxValue,
+}
+
+#[derive(AsCStr)]
+#[cstr(validate_charset="ascii_printable")]
+enum NonAscii {
+  #[cstr(name="Été")]
+  Summer,
+}
+
+#[derive(AsCStr)]
+#[cstr(validate_charset="c_identifier")]
+enum Unknown {
+  Foo,
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use cstr_enum::*;
+use std::ffi::CStr;
+
+#[derive(AsCStr)]
+#[allow(non_camel_case_types)]
+enum Constants {
+  Apple,
+  #[cstr(name = "pork")]
+  SmokedBacon { grams: u32 },
+  HTTPServer(u8),
+  r#Type,
+  Variants,
+  Snake_Case,
+}
+
+// Constants are usable in `const` contexts
+const PORK: &CStr = Constants::CSTR_SMOKED_BACON;
+
+fn main() {
+  assert_eq!(Constants::CSTR_APPLE, Constants::Apple.as_cstr());
+  assert_eq!(PORK.to_bytes(), b"pork");
+  assert_eq!(Constants::CSTR_HTTP_SERVER.to_bytes(), b"HTTPServer");
+  assert_eq!(Constants::CSTR_TYPE, Constants::r#Type.as_cstr());
+  assert_eq!(Constants::CSTR_SNAKE_CASE.to_bytes(), b"Snake_Case");
+  // `CSTR_VARIANTS` is still the list of every name
+  assert_eq!(Constants::CSTR_VARIANTS.len(), 6);
+  assert_eq!(Constants::Variants.as_cstr().to_bytes(), b"Variants");
+}
@@ -0,0 +1,9 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+enum Enum {
+  HttpServer,
+  HTTPServer,
+}
+
+fn main() {}
@@ -0,0 +1,56 @@
+use cstr_enum::*;
+
+#[path = "common/mod.rs"]
+mod common;
+use common::cstr;
+
+#[derive(Debug, PartialEq, AsCStr)]
+enum Command {
+  Help,
+  #[cstr(name = "history")]
+  History,
+  Quit,
+  #[cstr(name = "hint")]
+  Hint,
+}
+
+#[derive(Debug, PartialEq, FromCStr, AsCStr)]
+#[cstr(lookup = "binary_search")]
+enum Sorted {
+  #[cstr(name = "history")]
+  History,
+  #[cstr(name = "help")]
+  Help,
+  #[cstr(name = "quit")]
+  Quit,
+  #[cstr(name = "hint")]
+  Hint,
+}
+
+#[derive(Debug, PartialEq, AsCStr)]
+#[cstr(case_insensitive)]
+enum Insensitive {
+  Help,
+  Quit,
+}
+
+fn main() {
+  let found: Vec<_> = Command::variants_with_prefix(cstr(b"h\0")).collect();
+  assert_eq!(found, [Command::History, Command::Hint]);
+  let found: Vec<_> = Command::variants_with_prefix(cstr(b"Hel\0")).collect();
+  assert_eq!(found, [Command::Help]);
+  assert_eq!(Command::variants_with_prefix(cstr(b"x\0")).count(), 0);
+  assert_eq!(Command::variants_with_prefix(cstr(b"Quitting\0")).count(), 0);
+  assert_eq!(Command::variants_with_prefix(cstr(b"\0")).count(), 4);
+
+  let found: Vec<_> = Sorted::variants_with_prefix(cstr(b"h\0")).collect();
+  assert_eq!(found, [Sorted::Help, Sorted::Hint, Sorted::History]);
+  let found: Vec<_> = Sorted::variants_with_prefix(cstr(b"hi\0")).collect();
+  assert_eq!(found, [Sorted::Hint, Sorted::History]);
+  assert_eq!(Sorted::variants_with_prefix(cstr(b"a\0")).count(), 0);
+  assert_eq!(Sorted::variants_with_prefix(cstr(b"z\0")).count(), 0);
+  assert_eq!(Sorted::variants_with_prefix(cstr(b"\0")).count(), 4);
+
+  let found: Vec<_> = Insensitive::variants_with_prefix(cstr(b"QU\0")).collect();
+  assert_eq!(found, [Insensitive::Quit]);
+}
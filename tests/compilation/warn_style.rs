@@ -0,0 +1,33 @@
+#![deny(deprecated)]
+use cstr_enum::*;
+
+#[derive(AsCStr, FromCStr)]
+#[cstr(rename_all = "snake_case", acronyms("HTTP"), warn_style)]
+enum Enum0 {
+  HttpServer,
+  #[cstr(name = "NotSnake")]
+  Renamed,
+  #[cstr(name = "still_snake")]
+  Fine,
+  #[cstr(name = "HTTP_proxy")]
+  Acronym,
+}
+
+#[derive(AsCStr)]
+#[cstr(rename_all = "kebab-case", warn_style)]
+enum Enum1 {
+  #[cstr(name = "Upper-Kebab")]
+  Renamed,
+  #[cstr(name = "lower-kebab")]
+  Fine,
+}
+
+// Without the flag, overrides are left alone
+#[derive(AsCStr)]
+#[cstr(rename_all = "snake_case")]
+enum Enum2 {
+  #[cstr(name = "NotSnake")]
+  Renamed,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use cstr_enum::*;
+
+#[derive(AsCStr)]
+#[cstr(warn_style)]
+enum Enum0 {
+  #[cstr(name = "NotSnake")]
+  Renamed,
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+use cstr_enum::*;
+
+#[derive(Debug, PartialEq, AsCWStr, FromCWStr)]
+enum Enum0 {
+  Apple,
+  #[cstr(name="grüße")]
+  Greeting,
+}
+
+#[derive(Debug, PartialEq, AsCWStr, FromCWStr)]
+#[cstr(normalize="ascii_lowercase")]
+enum Enum1 {
+  Apple,
+  #[cstr(default)]
+  Unknown,
+}
+
+fn wide(s: &str) -> Vec<u16> {
+  s.encode_utf16().chain(Some(0)).collect()
+}
+
+fn main() {
+  assert_eq!(Enum0::Apple.as_cwstr(), wide("Apple"));
+  assert_eq!(Enum0::Greeting.as_cwstr(), wide("grüße"));
+  assert_eq!(Enum0::from_cwstr(Enum0::Apple.as_cwstr()), Ok(Enum0::Apple));
+  assert_eq!(Enum0::from_cwstr(Enum0::Greeting.as_cwstr()), Ok(Enum0::Greeting));
+  // without a nul terminator, and with units after it
+  assert_eq!(Enum0::from_cwstr(&wide("Apple")[..5]), Ok(Enum0::Apple));
+  assert_eq!(Enum0::from_cwstr(&wide("Apple\0Banana")), Ok(Enum0::Apple));
+  assert!(Enum0::from_cwstr(&wide("Banana")).is_err());
+  assert!(Enum0::from_cwstr(&wide("")).is_err());
+
+  assert_eq!(Enum1::Apple.as_cwstr(), wide("apple"));
+  assert_eq!(Enum1::from_cwstr(&wide("APPLE")), Ok(Enum1::Apple));
+  assert_eq!(Enum1::from_cwstr(&wide("Banana")), Ok(Enum1::Unknown));
+}
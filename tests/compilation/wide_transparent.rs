@@ -0,0 +1,18 @@
+use cstr_enum::*;
+use std::ffi::{CStr, CString};
+
+#[derive(AsCWStr)]
+enum Enum0<'a> {
+  Apple,
+  #[cstr(transparent)]
+  Held(&'a CStr),
+}
+
+#[derive(AsCWStr)]
+enum Enum1 {
+  Apple,
+  #[cstr(other)]
+  Unknown(CString),
+}
+
+fn main() {}
@@ -0,0 +1,60 @@
+//! A `no_std` crate, so `::std` does not resolve here and the derives must only use `core` paths or items
+//! re-exported by `cstr_enum`.  Features are unified within the workspace, so the code generated for the `alloc`
+//! and `std` features is checked here too.
+#![no_std]
+use cstr_enum::{AsCStr, FromCStr};
+use core::ffi::CStr;
+use core::fmt::Write;
+
+/// An enum with the usual naming options.
+#[derive(Debug, PartialEq, Eq, AsCStr, FromCStr)]
+#[cstr(case_insensitive, emit_index, emit_c_header)]
+pub enum Fruit {
+  Apple,
+  #[cstr(name = "banana", alias = "plantain")]
+  Banana,
+  #[cstr(default)]
+  Cherry,
+}
+
+/// An enum holding names it doesn't know.
+#[derive(Debug, PartialEq, Eq, AsCStr)]
+pub enum Colour<'a> {
+  Red,
+  #[cstr(transparent)]
+  Other(&'a CStr),
+}
+
+/// The names of every variant of [`Fruit`].
+pub const FRUIT_NAMES: &[&CStr] = Fruit::CSTR_VARIANTS;
+
+/// An enum whose error holds the unknown name, which needs the `alloc` feature.
+#[derive(Debug, PartialEq, Eq, FromCStr)]
+#[cstr(unknown_variant_error)]
+pub enum Vegetable {
+  Carrot,
+}
+
+/// An enum with the default error, which is a [`ParseCStrError`](cstr_enum::ParseCStrError) with the `rich_error`
+/// feature.
+#[derive(Debug, PartialEq, Eq, FromCStr)]
+pub enum Grain {
+  Rice,
+}
+
+/// Write the error from parsing `s` as a `T` to `out`, returning whether there was one.  The error is used as a
+/// `&dyn core::error::Error`, so this only compiles for errors which implement it without `std`.
+pub fn write_error<T, W>(s: &CStr, out: &mut W) -> bool
+where
+  T: FromCStr,
+  T::Err: core::error::Error + 'static,
+  W: Write,
+{
+  match T::from_cstr(s) {
+    Ok(_) => false,
+    Err(err) => {
+      let err: &dyn core::error::Error = &err;
+      write!(out, "{}", err).is_ok()
+    }
+  }
+}
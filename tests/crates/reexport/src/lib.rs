@@ -0,0 +1,45 @@
+//! Depends on `cstr_enum` under the name `cenum`, so `::cstr_enum` does not resolve in this crate and the
+//! derives must be pointed at the crate with `#[cstr(crate = "...")]`.
+pub use cenum as cstr_enum;
+
+mod fruit {
+  use cenum::{AsCStr, FromCStr};
+
+  /// An enum defined in this crate and re-exported at the root.
+  #[derive(Debug, PartialEq, Eq, AsCStr, FromCStr)]
+  #[cstr(crate = "::cenum")]
+  pub enum Fruit {
+    Apple,
+    #[cstr(name = "banana")]
+    Banana,
+  }
+}
+
+#[doc(inline)]
+pub use fruit::Fruit;
+
+/// Define an enum deriving `AsCStr` and `FromCStr`, with the derives found through this crate.
+///
+/// `$crate` is not expanded inside the string given to `#[cstr(crate = "...")]`, so this crate is named by
+/// its absolute path instead.  Callers do not need to depend on `cstr_enum` themselves.
+#[macro_export]
+macro_rules! define_enum {
+  ($name:ident { $( $variant:ident ),* $(,)? }) => {
+    #[derive(Debug, PartialEq, Eq, $crate::cstr_enum::AsCStr, $crate::cstr_enum::FromCStr)]
+    #[cstr(crate = "::cstr_enum_reexport::cstr_enum")]
+    pub enum $name {
+      $( $variant ),*
+    }
+  };
+}
+
+/// Like [`define_enum`], but relies on the default `::cstr_enum` path, so callers must depend on `cstr_enum`.
+#[macro_export]
+macro_rules! define_enum_default_path {
+  ($name:ident { $( $variant:ident ),* $(,)? }) => {
+    #[derive(Debug, PartialEq, Eq, $crate::cstr_enum::AsCStr, $crate::cstr_enum::FromCStr)]
+    pub enum $name {
+      $( $variant ),*
+    }
+  };
+}
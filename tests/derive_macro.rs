@@ -9,4 +9,115 @@ fn compile_tests() {
   cases.compile_fail("tests/compilation/non_enum.rs");
   cases.compile_fail("tests/compilation/name_nul_bytes.rs");
   cases.compile_fail("tests/compilation/name_on_enum.rs");
+  cases.pass("tests/compilation/normalize.rs");
+  cases.pass("tests/compilation/from_byte_iter.rs");
+  cases.pass("tests/compilation/globally_unique_pass.rs");
+  cases.compile_fail("tests/compilation/globally_unique_fail.rs");
+  cases.pass("tests/compilation/type_alias.rs");
+  cases.pass("tests/compilation/name_fn.rs");
+  cases.compile_fail("tests/compilation/name_fn_invalid.rs");
+  cases.pass("tests/compilation/prefix_fallback.rs");
+  cases.pass("tests/compilation/dispatch.rs");
+  cases.pass("tests/compilation/default.rs");
+  cases.pass("tests/compilation/other.rs");
+  cases.compile_fail("tests/compilation/default_and_other.rs");
+  cases.pass("tests/compilation/unit_error.rs");
+  cases.pass("tests/compilation/emit_index.rs");
+  cases.pass("tests/compilation/c_header.rs");
+  cases.pass("tests/compilation/separator_insensitive.rs");
+  cases.compile_fail("tests/compilation/separator_ambiguous.rs");
+  cases.pass("tests/compilation/owned_conversions.rs");
+  cases.compile_fail("tests/compilation/owned_conversions_invalid.rs");
+  cases.pass("tests/compilation/wide.rs");
+  cases.compile_fail("tests/compilation/wide_transparent.rs");
+  cases.pass("tests/compilation/total_bytes_max_pass.rs");
+  cases.compile_fail("tests/compilation/total_bytes_max_fail.rs");
+  cases.pass("tests/compilation/accept_ident.rs");
+  cases.compile_fail("tests/compilation/accept_ident_collision.rs");
+  cases.pass("tests/compilation/for_each_variant.rs");
+  cases.pass("tests/compilation/case_insensitive.rs");
+  cases.compile_fail("tests/compilation/case_insensitive_ambiguous.rs");
+  cases.pass("tests/compilation/discriminant_conversions.rs");
+  cases.pass("tests/compilation/c_module.rs");
+  cases.pass("tests/compilation/transparent.rs");
+  cases.pass("tests/compilation/unparseable.rs");
+  cases.compile_fail("tests/compilation/unparseable_deny.rs");
+  cases.pass("tests/compilation/cstr_const.rs");
+  cases.compile_fail("tests/compilation/cstr_const_invalid.rs");
+  cases.pass("tests/compilation/const_is.rs");
+  cases.pass("tests/compilation/name_template.rs");
+  cases.compile_fail("tests/compilation/name_template_invalid.rs");
+  cases.pass("tests/compilation/binary_search.rs");
+  cases.compile_fail("tests/compilation/binary_search_invalid.rs");
+  cases.pass("tests/compilation/cstr_for_str.rs");
+  cases.pass("tests/compilation/cstr_field.rs");
+  cases.pass("tests/compilation/debug_escaped.rs");
+  cases.pass("tests/compilation/rename_with.rs");
+  cases.compile_fail("tests/compilation/rename_with_invalid.rs");
+  cases.pass("tests/compilation/empty_input.rs");
+  cases.pass("tests/compilation/all_variants_and_names.rs");
+  cases.pass("tests/compilation/recursive.rs");
+  cases.pass("tests/compilation/as_cstr_catchall.rs");
+  cases.compile_fail("tests/compilation/as_cstr_catchall_exhaustive.rs");
+  cases.pass("tests/compilation/other_cow.rs");
+  cases.pass("tests/compilation/cfg_variants.rs");
+  cases.pass("tests/compilation/alias.rs");
+  cases.compile_fail("tests/compilation/alias_invalid.rs");
+  cases.pass("tests/compilation/name_for_tag.rs");
+  cases.pass("tests/compilation/round_trips.rs");
+  cases.compile_fail("tests/compilation/round_trips_invalid.rs");
+  cases.pass("tests/compilation/alias_repeated.rs");
+  cases.compile_fail("tests/compilation/alias_collision.rs");
+  cases.pass("tests/compilation/generics.rs");
+  cases.pass("tests/compilation/pascal_bytes.rs");
+  cases.pass("tests/compilation/empty_enum.rs");
+  cases.pass("tests/compilation/infallible.rs");
+  cases.pass("tests/compilation/rename_all.rs");
+  cases.compile_fail("tests/compilation/rename_all_invalid.rs");
+  cases.pass("tests/compilation/custom_error.rs");
+  cases.compile_fail("tests/compilation/custom_error_invalid.rs");
+  cases.pass("tests/compilation/unknown_variant.rs");
+  cases.compile_fail("tests/compilation/unknown_variant_invalid.rs");
+  cases.pass("tests/compilation/matched_name.rs");
+  cases.pass("tests/compilation/lookup_in.rs");
+  cases.pass("tests/compilation/name_trailing_nul.rs");
+  cases.compile_fail("tests/compilation/other_alias.rs");
+  cases.pass("tests/compilation/skip.rs");
+  cases.compile_fail("tests/compilation/skip_invalid.rs");
+  cases.pass("tests/compilation/variants_with_prefix.rs");
+  cases.pass("tests/compilation/variant_consts.rs");
+  cases.compile_fail("tests/compilation/variant_consts_collision.rs");
+  cases.pass("tests/compilation/duplicate_emit.rs");
+  cases.compile_fail("tests/compilation/duplicate_emit_invalid.rs");
+  cases.pass("tests/compilation/large_enum.rs");
+  cases.compile_fail("tests/compilation/duplicate_names.rs");
+  cases.pass("tests/compilation/concat_names.rs");
+  cases.compile_fail("tests/compilation/concat_names_invalid.rs");
+  cases.pass("tests/compilation/name_byte_str.rs");
+  cases.compile_fail("tests/compilation/name_byte_str_nul.rs");
+  cases.compile_fail("tests/compilation/name_byte_str_utf8.rs");
+  cases.pass("tests/compilation/validate_charset.rs");
+  cases.compile_fail("tests/compilation/validate_charset_invalid.rs");
+  cases.pass("tests/compilation/try_from.rs");
+  cases.pass("tests/compilation/from_cstr_bytes.rs");
+  cases.pass("tests/compilation/from_cstr_fuzzy.rs");
+  cases.pass("tests/compilation/emit_lengths.rs");
+  cases.pass("tests/compilation/display.rs");
+  cases.pass("tests/compilation/also_impl.rs");
+  cases.compile_fail("tests/compilation/also_impl_invalid.rs");
+  cases.compile_fail("tests/compilation/other_invalid.rs");
+  cases.pass("tests/compilation/as_cstr_deref.rs");
+  cases.pass("tests/compilation/outline.rs");
+  cases.compile_fail("tests/compilation/warn_style.rs");
+  cases.compile_fail("tests/compilation/warn_style_invalid.rs");
+  cases.pass("tests/compilation/cstr_variants.rs");
+  cases.pass("tests/compilation/closest_name.rs");
+  cases.pass("tests/compilation/emit_metadata.rs");
+  cases.compile_fail("tests/compilation/emit_metadata_invalid.rs");
+  cases.pass("tests/compilation/prefix_suffix.rs");
+  cases.compile_fail("tests/compilation/prefix_suffix_invalid.rs");
+  cases.pass("tests/compilation/trim.rs");
+  cases.pass("tests/compilation/as_cstr_const.rs");
+  #[cfg(not(feature = "serde"))]
+  cases.compile_fail("tests/compilation/serde_without_feature.rs");
 }
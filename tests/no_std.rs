@@ -0,0 +1,40 @@
+use cstr_enum::{AsCStr, FromCStr};
+use cstr_enum_no_std::{write_error, Colour, Fruit, Vegetable, FRUIT_NAMES};
+use std::ffi::{CStr, CString};
+
+fn cstr(bytes: &[u8]) -> &CStr {
+  CStr::from_bytes_with_nul(bytes).unwrap()
+}
+
+#[test]
+fn enum_from_no_std_crate() {
+  assert_eq!(Fruit::Banana.as_cstr().to_bytes(), b"banana");
+  assert_eq!(Fruit::from_cstr(cstr(b"PLANTAIN\0")), Ok(Fruit::Banana));
+  assert_eq!(Fruit::from_cstr(cstr(b"unknown\0")), Ok(Fruit::Cherry));
+  assert_eq!(Fruit::Cherry.index(), 2);
+  assert_eq!(FRUIT_NAMES, [cstr(b"Apple\0"), cstr(b"banana\0"), cstr(b"Cherry\0")]);
+  assert!(Fruit::to_c_header("fruit").contains("fruit_Apple = 0"));
+}
+
+#[test]
+fn transparent_in_no_std_crate() {
+  let held = cstr(b"mauve\0");
+  assert_eq!(Colour::Other(held).as_cstr(), held);
+  assert_eq!(CString::from(&Colour::Red).as_bytes(), b"Red");
+}
+
+#[test]
+fn core_error_in_no_std_crate() {
+  let mut out = String::new();
+  assert!(!write_error::<Vegetable, _>(cstr(b"Carrot\0"), &mut out));
+  assert!(write_error::<Vegetable, _>(cstr(b"potato\0"), &mut out));
+  assert!(out.contains("potato"));
+}
+
+#[cfg(feature = "rich_error")]
+#[test]
+fn rich_error_in_no_std_crate() {
+  let mut out = String::new();
+  assert!(write_error::<cstr_enum_no_std::Grain, _>(cstr(b"wheat\0"), &mut out));
+  assert_eq!(out, "unexpected string while parsing for Grain variant");
+}
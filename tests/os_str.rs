@@ -0,0 +1,28 @@
+// Only built on Unix with the `std` feature.
+#![cfg(all(unix, feature = "std"))]
+use cstr_enum::*;
+use std::ffi::{CStr, OsStr, OsString};
+use std::os::unix::ffi::OsStrExt;
+
+#[derive(AsCStr)]
+enum Constants<'a> {
+  Apple,
+  #[cstr(name=b"\xFFraw")]
+  Raw,
+  #[cstr(transparent)]
+  Other(&'a CStr),
+}
+
+#[test]
+fn as_os_str() {
+  assert_eq!(Constants::Apple.as_os_str(), OsStr::new("Apple"));
+  assert_eq!(Constants::Raw.as_os_str().as_bytes(), b"\xFFraw");
+  let held = CStr::from_bytes_with_nul(b"mauve\0").unwrap();
+  assert_eq!(Constants::Other(held).as_os_str(), OsStr::new("mauve"));
+}
+
+#[test]
+fn to_os_string() {
+  assert_eq!(Constants::Apple.to_os_string(), OsString::from("Apple"));
+  assert_eq!(Constants::Raw.to_os_string().as_bytes(), b"\xFFraw");
+}
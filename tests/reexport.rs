@@ -0,0 +1,34 @@
+use cstr_enum::{AsCStr, FromCStr};
+use cstr_enum_reexport::Fruit;
+use std::ffi::CStr;
+
+#[derive(Debug, PartialEq, cstr_enum_reexport::cstr_enum::AsCStr, cstr_enum_reexport::cstr_enum::FromCStr)]
+#[cstr(crate = "cstr_enum_reexport::cstr_enum")]
+enum Local {
+  Carrot,
+}
+
+#[test]
+fn enum_from_other_crate() {
+  assert_eq!(Fruit::Banana.as_cstr().to_bytes(), b"banana");
+  assert_eq!(Fruit::from_cstr(CStr::from_bytes_with_nul(b"Apple\0").unwrap()), Ok(Fruit::Apple));
+  assert_eq!(Fruit::CSTR_VARIANTS.len(), 2);
+}
+
+#[test]
+fn derive_through_reexport() {
+  assert_eq!(Local::Carrot.as_cstr().to_bytes(), b"Carrot");
+  assert_eq!(Local::from_cstr(CStr::from_bytes_with_nul(b"Carrot\0").unwrap()), Ok(Local::Carrot));
+}
+
+cstr_enum_reexport::define_enum!(FromMacro { Red, Green });
+
+cstr_enum_reexport::define_enum_default_path!(FromMacroDefaultPath { Blue });
+
+#[test]
+fn derive_inside_macro_from_other_crate() {
+  assert_eq!(FromMacro::Green.as_cstr().to_bytes(), b"Green");
+  assert_eq!(FromMacro::from_cstr(CStr::from_bytes_with_nul(b"Red\0").unwrap()), Ok(FromMacro::Red));
+  assert_eq!(FromMacroDefaultPath::Blue.as_cstr().to_bytes(), b"Blue");
+  assert_eq!(FromMacroDefaultPath::from_cstr(CStr::from_bytes_with_nul(b"Blue\0").unwrap()), Ok(FromMacroDefaultPath::Blue));
+}
@@ -0,0 +1,94 @@
+// Only built with `--features serde`.
+#![cfg(feature = "serde")]
+use cstr_enum::*;
+use serde::{Deserialize, Serialize};
+use std::ffi::CStr;
+
+#[derive(Debug, PartialEq, AsCStr, FromCStr)]
+#[cstr(no_string, serde)]
+enum Constants {
+  Apple,
+  #[cstr(name="pear", alias="poire")]
+  Pear,
+  #[cstr(name=b"\xFFraw")]
+  Raw,
+}
+
+#[derive(Debug, PartialEq, AsCStr)]
+#[cstr(serde)]
+enum Held<'a> {
+  Red,
+  #[cstr(transparent)]
+  Other(&'a CStr),
+}
+
+// Without `cstr(serde)`, the enum's own impls are used
+#[derive(Debug, PartialEq, AsCStr, FromCStr, Serialize, Deserialize)]
+enum Plain {
+  #[cstr(name="plain")]
+  Plain,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+  fruit: Constants,
+}
+
+#[test]
+fn serialize_as_str() {
+  assert_eq!(serde_json::to_string(&Constants::Pear).unwrap(), r#""pear""#);
+  let config = Config { fruit: Constants::Apple };
+  assert_eq!(serde_json::to_string(&config).unwrap(), r#"{"fruit":"Apple"}"#);
+  assert_eq!(serde_json::from_str::<Config>(&serde_json::to_string(&config).unwrap()).unwrap(), config);
+
+  let mauve = CStr::from_bytes_with_nul(b"mauve\0").unwrap();
+  assert_eq!(serde_json::to_string(&Held::Other(mauve)).unwrap(), r#""mauve""#);
+  assert_eq!(serde_json::to_string(&Held::Red).unwrap(), r#""Red""#);
+}
+
+#[test]
+fn serialize_rejects_non_utf8() {
+  let err = serde_json::to_string(&Constants::Raw).unwrap_err();
+  assert!(err.to_string().contains("of a Constants variant is not UTF-8"), "{}", err);
+  let invalid = CStr::from_bytes_with_nul(b"\xFF\0").unwrap();
+  assert!(serde_json::to_string(&Held::Other(invalid)).is_err());
+  assert!(bincode::serialize(&Constants::Raw).is_err());
+}
+
+#[test]
+fn deserialize_from_str() {
+  assert_eq!(serde_json::from_str::<Constants>(r#""pear""#).unwrap(), Constants::Pear);
+  assert_eq!(serde_json::from_str::<Constants>(r#""poire""#).unwrap(), Constants::Pear);
+  let config: Config = serde_json::from_str(r#"{ "fruit": "Apple" }"#).unwrap();
+  assert_eq!(config, Config { fruit: Constants::Apple });
+
+  // Escapes make serde_json build the string rather than borrowing it
+  assert_eq!(serde_json::from_str::<Constants>(r#""pe\u0061r""#).unwrap(), Constants::Pear);
+  let owned = serde_json::Value::String("Apple".to_owned());
+  assert_eq!(Constants::deserialize(owned).unwrap(), Constants::Apple);
+}
+
+#[test]
+fn deserialize_rejects_unknown() {
+  let err = serde_json::from_str::<Constants>(r#""apple""#).unwrap_err();
+  assert!(err.to_string().contains("a name of a Constants variant"), "{}", err);
+  // The nul byte would otherwise end the name after `pear`
+  assert!(serde_json::from_str::<Constants>(r#""pear\u0000x""#).is_err());
+}
+
+#[test]
+fn deserialize_from_bytes() {
+  let encoded = bincode::serialize(&b"\xFFraw"[..]).unwrap();
+  assert_eq!(bincode::deserialize::<Constants>(&encoded).unwrap(), Constants::Raw);
+  let encoded = bincode::serialize("pear").unwrap();
+  assert_eq!(bincode::deserialize::<Constants>(&encoded).unwrap(), Constants::Pear);
+  let encoded = bincode::serialize(&b"plum"[..]).unwrap();
+  assert!(bincode::deserialize::<Constants>(&encoded).is_err());
+}
+
+#[test]
+fn own_impls_without_flag() {
+  assert_eq!(serde_json::to_string(&Plain::Plain).unwrap(), r#""Plain""#);
+  assert_eq!(serde_json::from_str::<Plain>(r#""Plain""#).unwrap(), Plain::Plain);
+  assert!(serde_json::from_str::<Plain>(r#""plain""#).is_err());
+}
@@ -0,0 +1,69 @@
+// Without the `simd` feature this checks the scalar matcher against itself, so run it with `--features simd`.
+use cstr_enum::*;
+use std::ffi::{CStr, CString};
+
+#[derive(Debug, PartialEq, Clone, Copy, FromCStr, AsCStr)]
+#[cstr(case_insensitive)]
+enum Long {
+  #[cstr(name="VERY_LONG_CONSTANT_NAME_NUMBER_ONE")]
+  One,
+  #[cstr(name="VERY_LONG_CONSTANT_NAME_NUMBER_TWO")]
+  Two,
+  #[cstr(name="very@long[constant`name{number~three")]
+  Three,
+  #[cstr(name="short")]
+  Short,
+  #[cstr(name="\u{c9}t\u{e9}_non_ascii_bytes_in_a_longer_name")]
+  NonAscii,
+}
+
+const ALL: [Long; 5] = [Long::One, Long::Two, Long::Three, Long::Short, Long::NonAscii];
+
+/// The variant a scalar comparison of each name would find
+fn scalar(input: &[u8]) -> Option<Long> {
+  ALL.iter().copied().find(|v| v.as_cstr().to_bytes().eq_ignore_ascii_case(input))
+}
+
+fn check(input: &[u8]) {
+  let s = CString::new(input).unwrap();
+  assert_eq!(Long::from_cstr(&s).ok(), scalar(input), "input {:?}", s);
+}
+
+#[test]
+fn agrees_with_scalar() {
+  for v in &ALL {
+    let name = v.as_cstr().to_bytes();
+    check(name);
+    check(&name.to_ascii_lowercase());
+    check(&name.to_ascii_uppercase());
+    // differs in one byte, at every position
+    for i in 0..name.len() {
+      let mut miss = name.to_vec();
+      miss[i] = if miss[i] == b'x' { b'y' } else { b'x' };
+      check(&miss);
+      // bytes next to the letters, which only match themselves
+      for &b in b"@[`{" {
+        miss[i] = b;
+        check(&miss);
+      }
+      // toggling bit 5 of a non-letter must not match
+      miss[i] = name[i] ^ 0x20;
+      if miss[i] != 0 {
+        check(&miss);
+      }
+    }
+    check(&name[..name.len() - 1]);
+  }
+  check(b"");
+  check(b"very_long_constant_name_number_on");
+  check(b"VERY_LONG_CONSTANT_NAME_NUMBER_ONE_");
+  check(b"\xe9T\xc9_NON_ASCII_BYTES_IN_A_LONGER_NAME");
+}
+
+#[test]
+fn non_ascii_is_exact() {
+  let name = CStr::from_bytes_with_nul(b"\xc3\x89T\xc3\xa9_NON_ASCII_BYTES_IN_A_LONGER_NAME\0").unwrap();
+  assert_eq!(Long::from_cstr(name), Ok(Long::NonAscii));
+  let name = CStr::from_bytes_with_nul(b"\xe3\x89t\xc3\xa9_non_ascii_bytes_in_a_longer_name\0").unwrap();
+  assert!(Long::from_cstr(name).is_err());
+}
@@ -0,0 +1,29 @@
+// Only built with `--features std-impls`.
+#![cfg(feature = "std-impls")]
+use cstr_enum::*;
+use std::cmp::Ordering;
+use std::ffi::CStr;
+use std::net::Shutdown;
+
+fn cstr(bytes: &[u8]) -> &CStr {
+  CStr::from_bytes_with_nul(bytes).unwrap()
+}
+
+#[test]
+fn shutdown_round_trips() {
+  for v in [Shutdown::Read, Shutdown::Write, Shutdown::Both] {
+    assert_eq!(Shutdown::from_cstr(v.as_cstr()).ok(), Some(v));
+  }
+  assert_eq!(Shutdown::Write.as_cstr(), cstr(b"write\0"));
+  assert!(Shutdown::from_cstr(cstr(b"Read\0")).is_err());
+  assert!(Shutdown::from_cstr(cstr(b"\0")).is_err());
+}
+
+#[test]
+fn ordering_round_trips() {
+  for v in [Ordering::Less, Ordering::Equal, Ordering::Greater] {
+    assert_eq!(Ordering::from_cstr(v.as_cstr()), Ok(v));
+  }
+  assert_eq!(Ordering::Greater.as_cstr(), cstr(b"greater\0"));
+  assert!(Ordering::from_cstr(cstr(b"more\0")).is_err());
+}